@@ -0,0 +1,200 @@
+//! Binary zero-copy cache for probe atom data.
+//!
+//! `generate_probe_atoms` and `generate_probe_index` in
+//! `commands::atomize` re-parse the full `atoms.json` into a
+//! `HashMap<String, Value>` and rebuild the per-file interval trees on
+//! every invocation, which dominates startup on large crates. This module
+//! mirrors just the fields those two functions actually need into owned,
+//! `rkyv`-archivable structs, and writes/reads them as a binary cache file
+//! (`atoms.rkyv`) alongside `atoms.json`.
+//!
+//! The cache is purely an optimization: it's keyed on a schema version plus
+//! a hash of `atoms.json`, and any mismatch, missing file, or validation
+//! failure (a truncated or corrupted cache) is treated as a cache miss, not
+//! an error — callers always have the `atoms.json` parse to fall back to.
+
+use rkyv::rancor::Error as RkyvError;
+use rkyv::{Archive, Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Bump when `AtomRecord`/`IndexEntry`/`AtomCache`'s shape changes, so a
+/// cache written by an older build is rejected instead of misread.
+const CACHE_SCHEMA_VERSION: u32 = 1;
+
+/// The subset of an atoms.json entry that atomize's probe-atom consumers
+/// actually read.
+#[derive(Archive, Serialize, Deserialize, Debug, Clone)]
+#[rkyv(derive(Debug))]
+pub struct AtomRecord {
+    pub code_path: String,
+    pub code_module: String,
+    pub display_name: String,
+    pub lines_start: u32,
+    pub lines_end: u32,
+    pub dependencies: Vec<String>,
+    pub content: String,
+}
+
+/// One interval-tree entry: `probe_name`'s source spans the inclusive line
+/// range `[start, end]` in `code_path`.
+#[derive(Archive, Serialize, Deserialize, Debug, Clone)]
+#[rkyv(derive(Debug))]
+pub struct IndexEntry {
+    pub code_path: String,
+    pub start: u32,
+    pub end: u32,
+    pub probe_name: String,
+}
+
+#[derive(Archive, Serialize, Deserialize, Debug)]
+#[rkyv(derive(Debug))]
+struct AtomCache {
+    schema_version: u32,
+    /// SHA-256 hash (hex) of the atoms.json this cache was built from.
+    atoms_hash: String,
+    atoms: HashMap<String, AtomRecord>,
+    index: Vec<IndexEntry>,
+}
+
+/// Path of the binary cache for a given `atoms.json` path.
+pub fn cache_path(atoms_path: &Path) -> PathBuf {
+    atoms_path.with_file_name("atoms.rkyv")
+}
+
+fn hash_atoms_file(atoms_path: &Path) -> std::io::Result<String> {
+    let content = std::fs::read(atoms_path)?;
+    let mut hasher = Sha256::new();
+    hasher.update(&content);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn to_atom_record(atom_data: &Value) -> Option<AtomRecord> {
+    let code_path = atom_data.get("code-path")?.as_str()?.to_string();
+    let code_text = atom_data.get("code-text")?;
+    let lines_start = code_text.get("lines-start")?.as_u64()? as u32;
+    let lines_end = code_text.get("lines-end")?.as_u64()? as u32;
+    let code_module = atom_data
+        .get("code-module")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let display_name = atom_data
+        .get("display-name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let content = atom_data
+        .get("content")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let dependencies = atom_data
+        .get("dependencies")
+        .and_then(|v| v.as_array())
+        .map(|deps| deps.iter().filter_map(|d| d.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+
+    Some(AtomRecord {
+        code_path,
+        code_module,
+        display_name,
+        lines_start,
+        lines_end,
+        dependencies,
+        content,
+    })
+}
+
+/// Rebuild the atoms.json `Value` shape `AtomRecord` was distilled from, so
+/// a cache hit can feed straight back into the existing `Value`-based
+/// atomize pipeline.
+pub fn atom_record_to_value(record: &AtomRecord) -> Value {
+    serde_json::json!({
+        "code-path": record.code_path,
+        "code-module": record.code_module,
+        "display-name": record.display_name,
+        "code-text": {
+            "lines-start": record.lines_start,
+            "lines-end": record.lines_end,
+        },
+        "dependencies": record.dependencies,
+        "content": record.content,
+    })
+}
+
+/// Distill `probe_atoms` into `AtomRecord`s plus a flattened interval
+/// index, and write them to `atoms.rkyv` next to `atoms_path`. Best
+/// effort: a write failure is logged, not propagated, since the cache is
+/// purely an optimization.
+pub fn write(atoms_path: &Path, probe_atoms: &HashMap<String, Value>) {
+    let atoms_hash = match hash_atoms_file(atoms_path) {
+        Ok(hash) => hash,
+        Err(err) => {
+            eprintln!(
+                "Warning: failed to hash {} for atom cache: {err}",
+                atoms_path.display()
+            );
+            return;
+        }
+    };
+
+    let mut atoms = HashMap::with_capacity(probe_atoms.len());
+    let mut index = Vec::new();
+
+    for (probe_name, atom_data) in probe_atoms {
+        let Some(record) = to_atom_record(atom_data) else {
+            continue;
+        };
+        index.push(IndexEntry {
+            code_path: record.code_path.clone(),
+            start: record.lines_start,
+            end: record.lines_end,
+            probe_name: probe_name.clone(),
+        });
+        atoms.insert(probe_name.clone(), record);
+    }
+
+    let cache = AtomCache {
+        schema_version: CACHE_SCHEMA_VERSION,
+        atoms_hash,
+        atoms,
+        index,
+    };
+
+    let bytes = match rkyv::to_bytes::<RkyvError>(&cache) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            eprintln!("Warning: failed to serialize atom cache: {err}");
+            return;
+        }
+    };
+
+    if let Err(err) = std::fs::write(cache_path(atoms_path), &bytes) {
+        eprintln!("Warning: failed to write atom cache: {err}");
+    }
+}
+
+/// Load and validate the cache next to `atoms_path`, returning its atom
+/// records and flattened index on a hit. Returns `None` (meaning: re-parse
+/// `atoms.json`) if the cache is missing, fails bytecheck validation, was
+/// written by a different schema version, or no longer matches the current
+/// `atoms.json` hash.
+pub fn read(atoms_path: &Path) -> Option<(HashMap<String, AtomRecord>, Vec<IndexEntry>)> {
+    let bytes = std::fs::read(cache_path(atoms_path)).ok()?;
+
+    let archived = rkyv::access::<ArchivedAtomCache, RkyvError>(&bytes).ok()?;
+    if archived.schema_version != CACHE_SCHEMA_VERSION {
+        return None;
+    }
+
+    let current_hash = hash_atoms_file(atoms_path).ok()?;
+    if archived.atoms_hash.as_str() != current_hash {
+        return None;
+    }
+
+    let cache: AtomCache = rkyv::deserialize::<_, RkyvError>(archived).ok()?;
+    Some((cache.atoms, cache.index))
+}
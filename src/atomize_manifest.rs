@@ -0,0 +1,151 @@
+//! Content-addressed manifest for incremental atomize.
+//!
+//! `probe-verus atomize` re-walks and re-analyzes the whole project on
+//! every `atomize` invocation, even when nothing changed. This module
+//! hashes every Rust source file under the project root plus the
+//! `probe-verus` invocation's tool version and argument set, and compares
+//! that against a manifest saved from the last successful run
+//! (`atoms.manifest.json`, alongside `atoms.json`) so an unchanged project
+//! can skip the external call entirely and reuse `atoms.json` (and its
+//! [`crate::atom_cache`] binary cache).
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Bump when `Manifest`'s shape changes, so a manifest written by an older
+/// build is rejected instead of misread.
+const MANIFEST_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct Manifest {
+    schema_version: u32,
+    tool_version: String,
+    args: Vec<String>,
+    /// Source file path (relative to project root, `/`-separated) -> SHA-256 hex digest.
+    files: HashMap<String, String>,
+}
+
+/// Why the manifest check did or didn't find a reusable prior run.
+pub enum Check {
+    /// Every source digest and the tool version/args match the saved manifest.
+    Unchanged,
+    /// No manifest was saved (or it failed to parse), e.g. first atomize.
+    NoPreviousManifest,
+    /// `probe-verus`'s version or invocation arguments changed.
+    ToolOrArgsChanged,
+    /// At least one source file was added, removed, or edited; the
+    /// `path` entries are sorted and a removed file is suffixed `(removed)`.
+    FilesChanged(Vec<String>),
+}
+
+/// A manifest computed from the project's current state, ready to be
+/// compared against the one on disk and then saved.
+pub struct CurrentManifest(Manifest);
+
+/// Path of the manifest file for a given `atoms.json` path.
+pub fn manifest_path(atoms_path: &Path) -> PathBuf {
+    atoms_path.with_file_name("atoms.manifest.json")
+}
+
+fn is_ignored_dir(project_root: &Path, path: &Path) -> bool {
+    let Ok(rel) = path.strip_prefix(project_root) else {
+        return false;
+    };
+    rel.starts_with(".git") || rel.starts_with("target") || rel.starts_with(".verilib")
+}
+
+fn hash_sources(project_root: &Path) -> Result<HashMap<String, String>> {
+    let mut files = HashMap::new();
+
+    for entry in walkdir::WalkDir::new(project_root)
+        .into_iter()
+        .filter_entry(|e| !is_ignored_dir(project_root, e.path()))
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !path.extension().map_or(false, |ext| ext == "rs") {
+            continue;
+        }
+
+        let content = std::fs::read(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut hasher = Sha256::new();
+        hasher.update(&content);
+        let digest = format!("{:x}", hasher.finalize());
+
+        let rel = path
+            .strip_prefix(project_root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        files.insert(rel, digest);
+    }
+
+    Ok(files)
+}
+
+/// Hash the project's current `.rs` sources and pair them with the
+/// `probe-verus` tool version and argument set this run would use.
+pub fn compute(project_root: &Path, tool_version: &str, args: &[&str]) -> Result<CurrentManifest> {
+    Ok(CurrentManifest(Manifest {
+        schema_version: MANIFEST_SCHEMA_VERSION,
+        tool_version: tool_version.to_string(),
+        args: args.iter().map(|s| s.to_string()).collect(),
+        files: hash_sources(project_root)?,
+    }))
+}
+
+fn load(manifest_path: &Path) -> Option<Manifest> {
+    let content = std::fs::read_to_string(manifest_path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Compare `current` against the manifest saved at `manifest_path`.
+pub fn check(manifest_path: &Path, current: &CurrentManifest) -> Check {
+    let current = &current.0;
+
+    let Some(previous) = load(manifest_path) else {
+        return Check::NoPreviousManifest;
+    };
+
+    if previous.schema_version != current.schema_version
+        || previous.tool_version != current.tool_version
+        || previous.args != current.args
+    {
+        return Check::ToolOrArgsChanged;
+    }
+
+    let mut changed: Vec<String> = Vec::new();
+    for (path, hash) in &current.files {
+        match previous.files.get(path) {
+            Some(prev_hash) if prev_hash == hash => {}
+            _ => changed.push(path.clone()),
+        }
+    }
+    for path in previous.files.keys() {
+        if !current.files.contains_key(path) {
+            changed.push(format!("{path} (removed)"));
+        }
+    }
+    changed.sort();
+
+    if changed.is_empty() {
+        Check::Unchanged
+    } else {
+        Check::FilesChanged(changed)
+    }
+}
+
+/// Save `manifest` to disk at `manifest_path`, so the next atomize run can
+/// compare against it.
+pub fn save(manifest_path: &Path, manifest: &CurrentManifest) -> Result<()> {
+    if let Some(parent) = manifest_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(&manifest.0)?;
+    std::fs::write(manifest_path, content)?;
+    Ok(())
+}
@@ -1,18 +1,63 @@
 //! Certificate management for verilib structure.
 //!
-//! Handles creation and lookup of specification certificates.
+//! Handles creation and lookup of specification certificates, including
+//! provenance tracking so a cert can be detected as stale once the source
+//! it was issued against changes.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+/// Current cert file schema version. Bump when the `Cert` shape changes in a
+/// way that should force re-certification of everything on disk.
+pub const CERT_SCHEMA_VERSION: u32 = 3;
+
+/// A detached Ed25519 signature, from the verifier identified by
+/// `verifier_key_id`, over the cert's name, that key id, its `source_hash`
+/// and the signature's `timestamp`. `tool_version` is not part of the signed
+/// payload. See [`crate::trust`] for the acceptance policy these
+/// signatures are weighed against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertSignature {
+    /// Hex-encoded Ed25519 public key of the signer.
+    pub verifier_key_id: String,
+    /// Hex-encoded detached signature bytes.
+    pub signature: String,
+    pub timestamp: DateTime<Utc>,
+}
+
 /// Certificate data stored in cert files.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Cert {
     pub timestamp: DateTime<Utc>,
+    /// SHA-256 hash (hex) of the source region this cert was issued against.
+    #[serde(default)]
+    pub source_hash: String,
+    /// Version string of the tool that produced this cert, e.g. `probe-verus 0.3.1`.
+    #[serde(default)]
+    pub tool_version: String,
+    /// Schema version, so older cert files can be told apart from the current shape.
+    #[serde(default)]
+    pub schema_version: u32,
+    /// Detached signatures vouching for this cert's `source_hash`.
+    /// Only populated for signed verification certs; empty for spec certs.
+    #[serde(default)]
+    pub signatures: Vec<CertSignature>,
+}
+
+/// Freshness of a cert relative to the current source and tooling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CertStatus {
+    /// No cert file exists for this name.
+    Missing,
+    /// Cert exists and matches the current source hash and tool version.
+    Fresh,
+    /// Cert exists but the source hash or tool version has since changed.
+    Stale,
 }
 
 /// Encode an identifier for use as a filename.
@@ -29,6 +74,51 @@ pub fn decode_name(encoded: &str) -> String {
         .to_string()
 }
 
+/// Compute a SHA-256 hash (hex-encoded) of a source region.
+///
+/// When `line` is `Some` and non-zero, hashes just that line (the
+/// granularity `utils::parse_github_link` resolves to); otherwise hashes
+/// the whole file.
+pub fn hash_source_region(
+    project_root: &Path,
+    code_path: &str,
+    line: Option<u32>,
+) -> Result<String> {
+    let source_path = project_root.join(code_path);
+    let content = std::fs::read_to_string(&source_path)
+        .with_context(|| format!("Failed to read {}", source_path.display()))?;
+
+    let region = match line {
+        Some(line_number) if line_number > 0 => content
+            .lines()
+            .nth((line_number - 1) as usize)
+            .unwrap_or(""),
+        _ => content.as_str(),
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(region.as_bytes());
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Compute a SHA-256 hash (hex-encoded) of `text`, after trimming trailing
+/// whitespace from each line. Used as a content fingerprint (`code-hash`)
+/// that survives a trailing-whitespace cleanup, but still changes on any
+/// other edit to the extracted region, re-indentation included — so
+/// `atomize` can re-find an atom whose recorded line drifted instead of
+/// losing track of it.
+pub fn hash_normalized_content(text: &str) -> String {
+    let normalized: String = text
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut hasher = Sha256::new();
+    hasher.update(normalized.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
 /// Get the set of identifiers that already have certs.
 pub fn get_existing_certs(certs_dir: &Path) -> Result<HashSet<String>> {
     let mut existing = HashSet::new();
@@ -52,8 +142,14 @@ pub fn get_existing_certs(certs_dir: &Path) -> Result<HashSet<String>> {
     Ok(existing)
 }
 
-/// Create a cert file for a function.
-pub fn create_cert(certs_dir: &Path, name: &str) -> Result<PathBuf> {
+/// Create a cert file for a function, recording the source hash and tool
+/// version it was certified against.
+pub fn create_cert(
+    certs_dir: &Path,
+    name: &str,
+    source_hash: &str,
+    tool_version: &str,
+) -> Result<PathBuf> {
     std::fs::create_dir_all(certs_dir)?;
 
     let encoded_name = encode_name(name);
@@ -61,6 +157,10 @@ pub fn create_cert(certs_dir: &Path, name: &str) -> Result<PathBuf> {
 
     let cert = Cert {
         timestamp: Utc::now(),
+        source_hash: source_hash.to_string(),
+        tool_version: tool_version.to_string(),
+        schema_version: CERT_SCHEMA_VERSION,
+        signatures: Vec::new(),
     };
 
     let content = serde_json::to_string_pretty(&cert)?;
@@ -69,3 +169,219 @@ pub fn create_cert(certs_dir: &Path, name: &str) -> Result<PathBuf> {
     Ok(cert_path)
 }
 
+/// Load a cert file by name, if it exists.
+pub fn load_cert(certs_dir: &Path, name: &str) -> Result<Option<Cert>> {
+    let encoded_name = encode_name(name);
+    let cert_path = certs_dir.join(format!("{}.json", encoded_name));
+
+    if !cert_path.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&cert_path)
+        .with_context(|| format!("Failed to read cert {}", cert_path.display()))?;
+    let cert: Cert = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse cert {}", cert_path.display()))?;
+    Ok(Some(cert))
+}
+
+/// Create or update a signed verification cert for `name`, adding a fresh
+/// detached signature from `signing_key` over its name, the signer's key
+/// id, its current `source_hash` and the signing time.
+///
+/// If the existing cert (if any) was issued against a different source
+/// hash or tool version, its prior signatures are no longer meaningful and
+/// are discarded; this verifier's signature starts a new signature set. If
+/// this verifier already signed the current `(source_hash, tool_version)`,
+/// its signature is refreshed rather than duplicated.
+pub fn sign_cert(
+    certs_dir: &Path,
+    name: &str,
+    source_hash: &str,
+    tool_version: &str,
+    signing_key: &ed25519_dalek::SigningKey,
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(certs_dir)?;
+
+    let encoded_name = encode_name(name);
+    let cert_path = certs_dir.join(format!("{}.json", encoded_name));
+
+    let mut cert = load_cert(certs_dir, name)?.unwrap_or(Cert {
+        timestamp: Utc::now(),
+        source_hash: source_hash.to_string(),
+        tool_version: tool_version.to_string(),
+        schema_version: CERT_SCHEMA_VERSION,
+        signatures: Vec::new(),
+    });
+
+    if cert.source_hash != source_hash
+        || cert.tool_version != tool_version
+        || cert.schema_version != CERT_SCHEMA_VERSION
+    {
+        cert.source_hash = source_hash.to_string();
+        cert.tool_version = tool_version.to_string();
+        cert.schema_version = CERT_SCHEMA_VERSION;
+        cert.signatures.clear();
+    }
+
+    let timestamp = Utc::now();
+    let verifier_key_id = crate::trust::key_id(signing_key);
+    let payload = crate::trust::signing_payload(
+        name,
+        &verifier_key_id,
+        source_hash,
+        &timestamp.to_rfc3339(),
+    );
+    let signature = crate::trust::sign(signing_key, &payload);
+
+    cert.signatures.retain(|s| s.verifier_key_id != verifier_key_id);
+    cert.signatures.push(CertSignature {
+        verifier_key_id,
+        signature,
+        timestamp,
+    });
+    cert.timestamp = timestamp;
+
+    let content = serde_json::to_string_pretty(&cert)?;
+    std::fs::write(&cert_path, content)?;
+
+    Ok(cert_path)
+}
+
+/// Sum the trust weight of `cert`'s signatures that validate against its
+/// name and claimed `source_hash` and are reachable in
+/// `trust_config`'s delegation graph. Each signer is counted at most once.
+pub fn cert_trust_weight(
+    name: &str,
+    cert: &Cert,
+    source_hash: &str,
+    tool_version: &str,
+    trust_config: &crate::trust::TrustConfig,
+) -> u32 {
+    if cert.source_hash != source_hash || cert.tool_version != tool_version {
+        return 0;
+    }
+
+    let mut counted = HashSet::new();
+    let mut total = 0u32;
+    for sig in &cert.signatures {
+        if !counted.insert(sig.verifier_key_id.clone()) {
+            continue;
+        }
+
+        let payload = crate::trust::signing_payload(
+            name,
+            &sig.verifier_key_id,
+            source_hash,
+            &sig.timestamp.to_rfc3339(),
+        );
+        if !crate::trust::verify_signature(&sig.verifier_key_id, &payload, &sig.signature) {
+            continue;
+        }
+
+        total += trust_config.weight_of(&sig.verifier_key_id);
+    }
+
+    total
+}
+
+/// Whether `cert` accumulates enough trust weight to count as verified.
+pub fn is_cert_accepted(
+    name: &str,
+    cert: &Cert,
+    source_hash: &str,
+    tool_version: &str,
+    trust_config: &crate::trust::TrustConfig,
+) -> bool {
+    cert_trust_weight(name, cert, source_hash, tool_version, trust_config) >= trust_config.threshold
+}
+
+/// Names whose verification cert accumulates enough trust weight to count
+/// as accepted, given each name's current `(source_hash, tool_version)`.
+/// Names with no cert, or whose cert doesn't reach the trust threshold,
+/// are omitted.
+pub fn accepted_certs(
+    certs_dir: &Path,
+    current: &HashMap<String, (String, String)>,
+    trust_config: &crate::trust::TrustConfig,
+) -> Result<HashSet<String>> {
+    let mut accepted = HashSet::new();
+
+    for (name, (hash, tool_version)) in current {
+        if let Some(cert) = load_cert(certs_dir, name)? {
+            if is_cert_accepted(name, &cert, hash, tool_version, trust_config) {
+                accepted.insert(name.clone());
+            }
+        }
+    }
+
+    Ok(accepted)
+}
+
+/// Delete a cert file for a function.
+pub fn delete_cert(certs_dir: &Path, name: &str) -> Result<Option<PathBuf>> {
+    let encoded_name = encode_name(name);
+    let cert_path = certs_dir.join(format!("{}.json", encoded_name));
+
+    if cert_path.exists() {
+        std::fs::remove_file(&cert_path)?;
+        Ok(Some(cert_path))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Determine whether a cert is fresh, stale, or missing, by comparing it
+/// against the current source hash and tool version.
+pub fn cert_status(
+    certs_dir: &Path,
+    name: &str,
+    current_hash: &str,
+    current_tool_version: &str,
+) -> Result<CertStatus> {
+    let encoded_name = encode_name(name);
+    let cert_path = certs_dir.join(format!("{}.json", encoded_name));
+
+    if !cert_path.exists() {
+        return Ok(CertStatus::Missing);
+    }
+
+    let content = std::fs::read_to_string(&cert_path)
+        .with_context(|| format!("Failed to read cert {}", cert_path.display()))?;
+    let cert: Cert = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse cert {}", cert_path.display()))?;
+
+    if cert.schema_version == CERT_SCHEMA_VERSION
+        && cert.source_hash == current_hash
+        && cert.tool_version == current_tool_version
+    {
+        Ok(CertStatus::Fresh)
+    } else {
+        Ok(CertStatus::Stale)
+    }
+}
+
+/// Partition a set of names into fresh and stale certs, given each name's
+/// current `(source_hash, tool_version)`. Names with no existing cert are
+/// omitted from both sets.
+pub fn partition_stale_certs(
+    certs_dir: &Path,
+    current: &HashMap<String, (String, String)>,
+) -> Result<(HashSet<String>, HashSet<String>)> {
+    let mut fresh = HashSet::new();
+    let mut stale = HashSet::new();
+
+    for (name, (hash, tool_version)) in current {
+        match cert_status(certs_dir, name, hash, tool_version)? {
+            CertStatus::Fresh => {
+                fresh.insert(name.clone());
+            }
+            CertStatus::Stale => {
+                stale.insert(name.clone());
+            }
+            CertStatus::Missing => {}
+        }
+    }
+
+    Ok((fresh, stale))
+}
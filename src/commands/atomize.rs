@@ -2,18 +2,72 @@
 //!
 //! Enrich structure files with metadata from SCIP atoms or blueprint.
 
+use crate::atom_cache::IndexEntry;
+use crate::atomize_manifest;
+use crate::certs::hash_normalized_content;
 use crate::config::constants::PROBE_PREFIX;
 use crate::config::ConfigPaths;
-use crate::utils::{check_probe_verus_or_exit, parse_frontmatter, run_command};
+use crate::utils::{
+    check_probe_verus_or_exit, frontmatter_to_typed, get_tool_version, levenshtein, parse_frontmatter, run_command,
+    status,
+};
 use crate::{StructureForm, StructureType};
 use anyhow::{bail, Context, Result};
 use intervaltree::IntervalTree;
+use pulldown_cmark::{CodeBlockKind, Event, Parser as MarkdownParser, Tag};
+use regex::Regex;
+use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+/// Structured dalek-lite probe frontmatter, following the Test262 metadata
+/// model (description/features/includes/flags/negative). `code-path` and
+/// `code-line` are required — a file missing either fails schema
+/// validation with a hard error rather than being silently skipped.
+/// `code-name` stays optional since `create` writes it as `null` until a
+/// later `atomize` run resolves it.
+#[derive(Debug, Clone, Deserialize)]
+struct ProbeFrontmatter {
+    #[serde(rename = "code-path")]
+    code_path: String,
+    #[serde(rename = "code-line")]
+    code_line: u32,
+    #[serde(rename = "code-name", default)]
+    code_name: Option<String>,
+    /// Language/verifier features this atom exercises.
+    #[serde(default)]
+    features: Vec<String>,
+    /// Other atoms (by `code-name`) this one depends on; each must resolve
+    /// in `probe_atoms`, or extraction fails with a hard error.
+    #[serde(default)]
+    includes: Vec<String>,
+    /// Free-form extraction/verification flags.
+    #[serde(default)]
+    flags: Vec<String>,
+    /// Expected failure, if this atom documents one.
+    #[serde(default)]
+    negative: Option<NegativeSpec>,
+}
+
+/// The phase and error type an atom is expected to fail at, mirroring
+/// Test262's `negative` metadata.
+#[derive(Debug, Clone, Deserialize)]
+struct NegativeSpec {
+    phase: String,
+    #[serde(rename = "type")]
+    error_type: String,
+}
+
 /// Run the atomize subcommand.
-pub fn run(project_root: PathBuf, update_stubs: bool) -> Result<()> {
+pub fn run(
+    project_root: PathBuf,
+    update_stubs: bool,
+    force: bool,
+    line_tolerance: u32,
+    check: bool,
+    generate_harness: bool,
+) -> Result<()> {
     let project_root = project_root.canonicalize()
         .context("Failed to resolve project root")?;
     let config = ConfigPaths::load(&project_root)?;
@@ -23,10 +77,28 @@ pub fn run(project_root: PathBuf, update_stubs: bool) -> Result<()> {
 
     match structure_type {
         StructureType::Blueprint => {
+            if line_tolerance > 0 {
+                eprintln!("Warning: --line-tolerance is ignored for blueprint type");
+            }
+            if check {
+                eprintln!("Warning: --check is ignored for blueprint type");
+            }
+            if generate_harness {
+                eprintln!("Warning: --generate-harness is ignored for blueprint type");
+            }
             run_blueprint_atomize(&config, structure_form)?;
         }
         StructureType::DalekLite => {
-            run_dalek_atomize(&project_root, &config, structure_form, update_stubs)?;
+            run_dalek_atomize(
+                &project_root,
+                &config,
+                structure_form,
+                update_stubs,
+                force,
+                line_tolerance,
+                check,
+                generate_harness,
+            )?;
         }
     }
 
@@ -45,7 +117,7 @@ fn run_blueprint_atomize(config: &ConfigPaths, structure_form: StructureForm) ->
         );
     }
 
-    println!("Loading blueprint from {}...", config.blueprint_json_path.display());
+    status!("Loading blueprint from {}...", config.blueprint_json_path.display());
     let content = std::fs::read_to_string(&config.blueprint_json_path)?;
     let blueprint_data: HashMap<String, Value> = serde_json::from_str(&content)?;
 
@@ -55,25 +127,25 @@ fn run_blueprint_atomize(config: &ConfigPaths, structure_form: StructureForm) ->
                 bail!("{} not found", config.structure_json_path.display());
             }
 
-            println!("Loading structure from {}...", config.structure_json_path.display());
+            status!("Loading structure from {}...", config.structure_json_path.display());
             let content = std::fs::read_to_string(&config.structure_json_path)?;
             let structure: HashMap<String, Value> = serde_json::from_str(&content)?;
 
-            println!("Populating structure metadata from blueprint...");
+            status!("Populating structure metadata from blueprint...");
             let metadata = populate_blueprint_json_metadata(&structure, &blueprint_data)?;
 
-            println!("Saving metadata to {}...", config.structure_meta_path.display());
+            status!("Saving metadata to {}...", config.structure_meta_path.display());
             let content = serde_json::to_string_pretty(&metadata)?;
             std::fs::write(&config.structure_meta_path, content)?;
-            println!("Done.");
+            status!("Done.");
         }
         StructureForm::Files => {
-            println!(
+            status!(
                 "Populating blueprint metadata files in {}...",
                 config.structure_root.display()
             );
             populate_blueprint_files_metadata(&blueprint_data, &config.structure_root)?;
-            println!("Done.");
+            status!("Done.");
         }
     }
 
@@ -138,8 +210,8 @@ fn populate_blueprint_json_metadata(
         created_count += 1;
     }
 
-    println!("Metadata entries created: {}", created_count);
-    println!("Skipped: {}", skipped_count);
+    status!("Metadata entries created: {}", created_count);
+    status!("Skipped: {}", skipped_count);
 
     Ok(result)
 }
@@ -229,8 +301,8 @@ fn populate_blueprint_files_metadata(
         created_count += 1;
     }
 
-    println!("Metadata files created: {}", created_count);
-    println!("Skipped: {}", skipped_count);
+    status!("Metadata files created: {}", created_count);
+    status!("Skipped: {}", skipped_count);
 
     Ok(())
 }
@@ -244,45 +316,72 @@ fn run_dalek_atomize(
     config: &ConfigPaths,
     structure_form: StructureForm,
     update_stubs: bool,
+    force: bool,
+    line_tolerance: u32,
+    check: bool,
+    generate_harness: bool,
 ) -> Result<()> {
-    let probe_atoms = generate_probe_atoms(project_root, &config.atoms_path)?;
+    let (probe_atoms, cached_index) = generate_probe_atoms(project_root, &config.atoms_path, force)?;
     let probe_atoms = filter_probe_atoms(&probe_atoms, PROBE_PREFIX);
-    let probe_index = generate_probe_index(&probe_atoms);
+    let probe_index = generate_probe_index(cached_index, &probe_atoms);
 
     match structure_form {
         StructureForm::Json => {
+            if check {
+                eprintln!("Warning: --check is only supported for the files form; running a normal atomize instead");
+            }
+            if generate_harness {
+                eprintln!("Warning: --generate-harness is only supported for the files form");
+            }
+
             if !config.structure_json_path.exists() {
                 bail!("{} not found", config.structure_json_path.display());
             }
 
-            println!("Loading structure from {}...", config.structure_json_path.display());
+            status!("Loading structure from {}...", config.structure_json_path.display());
             let content = std::fs::read_to_string(&config.structure_json_path)?;
             let structure: HashMap<String, Value> = serde_json::from_str(&content)?;
 
             // Sync to get code-names (in memory)
-            println!("Syncing structure with probe atoms...");
-            let structure = sync_structure_json_with_atoms(structure, &probe_index, &probe_atoms)?;
+            status!("Syncing structure with probe atoms...");
+            let structure =
+                sync_structure_json_with_atoms(structure, &probe_index, &probe_atoms, line_tolerance)?;
 
-            println!("Enriching structure with atom metadata...");
-            let enriched = enrich_structure_json(&structure, &probe_atoms)?;
+            status!("Enriching structure with atom metadata...");
+            let enriched = enrich_structure_json(&structure, &probe_atoms, project_root)?;
 
-            println!("Saving enriched structure to {}...", config.structure_json_path.display());
+            status!("Saving enriched structure to {}...", config.structure_json_path.display());
             let content = serde_json::to_string_pretty(&enriched)?;
             std::fs::write(&config.structure_json_path, content)?;
-            println!("Done.");
+            status!("Done.");
         }
         StructureForm::Files => {
+            if check {
+                return check_structure_files_drift(&probe_atoms, &probe_index, &config.structure_root);
+            }
+
             if update_stubs {
-                println!(
+                status!(
                     "Syncing structure files in {} with probe atoms...",
                     config.structure_root.display()
                 );
-                sync_structure_files_with_atoms(&probe_index, &probe_atoms, &config.structure_root)?;
+                sync_structure_files_with_atoms(
+                    &probe_index,
+                    &probe_atoms,
+                    &config.structure_root,
+                    line_tolerance,
+                )?;
             }
 
-            println!("Populating structure metadata files...");
-            populate_structure_files_metadata(&probe_atoms, &probe_index, &config.structure_root, project_root)?;
-            println!("Done.");
+            status!("Populating structure metadata files...");
+            populate_structure_files_metadata(
+                &probe_atoms,
+                &probe_index,
+                &config.structure_root,
+                project_root,
+                generate_harness,
+            )?;
+            status!("Done.");
         }
     }
 
@@ -290,36 +389,63 @@ fn run_dalek_atomize(
 }
 
 /// Run probe-verus atomize on the project and save results to atoms.json.
-fn generate_probe_atoms(project_root: &Path, atoms_path: &Path) -> Result<HashMap<String, Value>> {
+/// Before shelling out, checks the [`crate::atomize_manifest`] saved from
+/// the last run: if every source file's digest plus the tool version and
+/// invocation arguments are unchanged, the external call is skipped
+/// entirely and the previous `atoms.json` (or its binary cache) is reused.
+/// `force` bypasses this check unconditionally.
+fn generate_probe_atoms(
+    project_root: &Path,
+    atoms_path: &Path,
+    force: bool,
+) -> Result<(HashMap<String, Value>, Option<Vec<IndexEntry>>)> {
     check_probe_verus_or_exit()?;
 
     if let Some(parent) = atoms_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
-    println!("Running probe-verus atomize on {}...", project_root.display());
-
-    let output = run_command(
-        "probe-verus",
-        &[
-            "atomize",
-            project_root.to_str().unwrap(),
-            "-o",
-            atoms_path.to_str().unwrap(),
-            "-r",
-        ],
-        None,
-    )?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        eprintln!("Error: probe-verus atomize failed.");
-        if !stderr.is_empty() {
-            eprintln!("{}", stderr);
-        }
-        bail!("probe-verus atomize failed");
+    let args = [
+        "atomize",
+        project_root.to_str().unwrap(),
+        "-o",
+        atoms_path.to_str().unwrap(),
+        "-r",
+    ];
+    let manifest_path = atomize_manifest::manifest_path(atoms_path);
+
+    if !force && atoms_path.exists() {
+        if let Ok(tool_version) = get_tool_version("probe-verus") {
+            if let Ok(current_manifest) = atomize_manifest::compute(project_root, &tool_version, &args) {
+                match atomize_manifest::check(&manifest_path, &current_manifest) {
+                    atomize_manifest::Check::Unchanged => {
+                        status!(
+                            "Sources unchanged since last atomize; reusing {}",
+                            atoms_path.display()
+                        );
+                        return load_atoms_json(atoms_path);
+                    }
+                    atomize_manifest::Check::FilesChanged(changed) => {
+                        status!("Re-atomizing: {} file(s) changed since last run:", changed.len());
+                        for path in &changed {
+                            status!("  {}", path);
+                        }
+                    }
+                    atomize_manifest::Check::ToolOrArgsChanged => {
+                        status!("Re-atomizing: probe-verus version or arguments changed since last run");
+                    }
+                    atomize_manifest::Check::NoPreviousManifest => {
+                        status!("Re-atomizing: no previous atomize manifest found");
+                    }
+                }
+            }
+        }
     }
 
+    status!("Running probe-verus atomize on {}...", project_root.display());
+
+    run_command("probe-verus", &args, None)?;
+
     // Clean up generated intermediate files
     for cleanup_file in ["data/index.scip", "data/index.scip.json"] {
         let cleanup_path = project_root.join(cleanup_file);
@@ -335,11 +461,44 @@ fn generate_probe_atoms(project_root: &Path, atoms_path: &Path) -> Result<HashMa
         }
     }
 
-    println!("Results saved to {}", atoms_path.display());
+    status!("Results saved to {}", atoms_path.display());
+
+    let atoms = load_atoms_json(atoms_path)?;
+
+    if let Ok(tool_version) = get_tool_version("probe-verus") {
+        if let Ok(current_manifest) = atomize_manifest::compute(project_root, &tool_version, &args) {
+            if let Err(err) = atomize_manifest::save(&manifest_path, &current_manifest) {
+                eprintln!("Warning: failed to save atomize manifest: {err}");
+            }
+        }
+    }
+
+    Ok(atoms)
+}
+
+/// Load atoms from the binary cache when it's valid for `atoms_path`,
+/// otherwise parse `atoms.json` directly and (re)populate the cache.
+/// Reads the cache (if any) exactly once and hands back its flattened
+/// interval index alongside the atoms map, so `generate_probe_index`
+/// doesn't need to read and deserialize the same cache file again.
+fn load_atoms_json(atoms_path: &Path) -> Result<(HashMap<String, Value>, Option<Vec<IndexEntry>>)> {
+    if let Some((cached_atoms, cached_index)) = crate::atom_cache::read(atoms_path) {
+        status!(
+            "Loaded {} atom(s) from cache {}",
+            cached_atoms.len(),
+            crate::atom_cache::cache_path(atoms_path).display()
+        );
+        let atoms = cached_atoms
+            .into_iter()
+            .map(|(name, record)| (name, crate::atom_cache::atom_record_to_value(&record)))
+            .collect();
+        return Ok((atoms, Some(cached_index)));
+    }
 
     let content = std::fs::read_to_string(atoms_path)?;
     let atoms: HashMap<String, Value> = serde_json::from_str(&content)?;
-    Ok(atoms)
+    crate::atom_cache::write(atoms_path, &atoms);
+    Ok((atoms, None))
 }
 
 /// Filter probe atoms to only those where probe-name starts with prefix.
@@ -352,8 +511,30 @@ fn filter_probe_atoms(probe_atoms: &HashMap<String, Value>, prefix: &str) -> Has
         .collect()
 }
 
-/// Build an interval tree index for fast line-based lookups.
-fn generate_probe_index(probe_atoms: &HashMap<String, Value>) -> HashMap<String, IntervalTree<u32, String>> {
+/// Build an interval tree index for fast line-based lookups. `cached_index`
+/// is the flattened index from the same cache read `load_atoms_json`
+/// already performed (`None` on a cache miss) -- reused here instead of
+/// reading and deserializing `atoms.rkyv` a second time. When present,
+/// its entries are used directly (restricted to the names present in
+/// `probe_atoms`), skipping the per-atom `Value` walk below.
+fn generate_probe_index(
+    cached_index: Option<Vec<IndexEntry>>,
+    probe_atoms: &HashMap<String, Value>,
+) -> HashMap<String, IntervalTree<u32, String>> {
+    if let Some(cached_index) = cached_index {
+        let mut trees: HashMap<String, Vec<(std::ops::Range<u32>, String)>> = HashMap::new();
+        for entry in cached_index {
+            if !probe_atoms.contains_key(&entry.probe_name) {
+                continue;
+            }
+            trees
+                .entry(entry.code_path)
+                .or_default()
+                .push((entry.start..entry.end + 1, entry.probe_name));
+        }
+        return trees.into_iter().map(|(k, v)| (k, v.into_iter().collect())).collect();
+    }
+
     let mut trees: HashMap<String, Vec<(std::ops::Range<u32>, String)>> = HashMap::new();
 
     for (probe_name, atom_data) in probe_atoms {
@@ -389,12 +570,82 @@ fn generate_probe_index(probe_atoms: &HashMap<String, Value>) -> HashMap<String,
         .collect()
 }
 
+/// Find up to `limit` keys in `candidates` within edit distance of
+/// `target`, closest first. The distance threshold scales with `target`'s
+/// length (`max(2, len/3)`) so a long renamed identifier still surfaces a
+/// hint while short names don't get flooded with unrelated near-matches.
+fn nearest_names<'a>(target: &str, candidates: impl Iterator<Item = &'a str>, limit: usize) -> Vec<&'a str> {
+    let threshold = (target.len() / 3).max(2);
+    let mut scored: Vec<(usize, &str)> = candidates
+        .filter_map(|candidate| {
+            let distance = levenshtein(target, candidate);
+            (distance <= threshold).then_some((distance, candidate))
+        })
+        .collect();
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    scored.into_iter().take(limit).map(|(_, name)| name).collect()
+}
+
+/// Render `suggestions` (from [`nearest_names`]) as a trailing warning clause.
+fn format_suggestions(suggestions: &[&str]) -> String {
+    if suggestions.is_empty() {
+        "no close matches found".to_string()
+    } else {
+        format!("did you mean: {}?", suggestions.join(", "))
+    }
+}
+
+/// If an atom exists at a different `code-path` than the missing one but
+/// shares its basename or the entry's `display-name`, it's the likely
+/// target of a file move/rename — surface it instead of a bare "not found".
+fn suggest_move_target(entry: &Value, code_path: &str, probe_atoms: &HashMap<String, Value>) -> Option<String> {
+    let basename = Path::new(code_path).file_name().and_then(|f| f.to_str());
+    let display_name = entry.get("display-name").and_then(|v| v.as_str());
+
+    probe_atoms.values().find_map(|atom| {
+        let atom_path = atom.get("code-path").and_then(|v| v.as_str())?;
+        if atom_path == code_path {
+            return None;
+        }
+
+        let basename_matches = basename.is_some()
+            && basename == Path::new(atom_path).file_name().and_then(|f| f.to_str());
+        let display_matches =
+            display_name.is_some() && display_name == atom.get("display-name").and_then(|v| v.as_str());
+
+        (basename_matches || display_matches).then(|| atom_path.to_string())
+    })
+}
+
+/// Find the smallest interval in `tree` that contains `line` (i.e.
+/// `range.start <= line < range.end`), breaking ties by minimal span. Used
+/// as a fallback when a frontmatter reference points at a line inside a
+/// function/struct body rather than exactly at its start. Returns the
+/// atom's probe name and its `(start, end)`.
+fn find_smallest_enclosing(tree: &IntervalTree<u32, String>, line: u32) -> Option<(String, u32, u32)> {
+    tree.query(line..line + 1)
+        .min_by_key(|iv| iv.range.end - iv.range.start)
+        .map(|iv| (iv.value.clone(), iv.range.start, iv.range.end))
+}
+
 /// Update a structure entry with probe atom data.
+///
+/// Matching against `probe_index` tries three tiers in order: an interval
+/// starting exactly at the recorded `code-line`; failing that, the
+/// smallest interval that merely *contains* `code-line` (so a reference
+/// anchored partway into a function/struct body still resolves, with a
+/// note printed since the author could tighten it to the exact start);
+/// and finally, if `line_tolerance > 0`, the nearest interval starting
+/// within `line_tolerance` lines (ties broken by smallest span) — so an
+/// ordinary edit that shifts a definition by a few lines doesn't drop the
+/// entry's `code-name`. Whichever tier matches, `code-line` is rewritten
+/// to the matched start when it differs from the recorded one.
 fn update_entry_from_atoms(
     entry: &Value,
     probe_index: &HashMap<String, IntervalTree<u32, String>>,
     probe_atoms: &HashMap<String, Value>,
     context: &str,
+    line_tolerance: u32,
 ) -> Result<(Value, Option<String>)> {
     let code_path = entry.get("code-path").and_then(|v| v.as_str());
     let line_start = entry.get("code-line").and_then(|v| v.as_u64()).map(|l| l as u32);
@@ -438,9 +689,10 @@ fn update_entry_from_atoms(
 
             return Ok((updated, None));
         } else {
+            let suggestions = nearest_names(probe_name, probe_atoms.keys().map(String::as_str), 3);
             eprintln!(
-                "WARNING: code-name '{}' not found in probe_atoms for {}, looking up by code-path/code-line",
-                probe_name, context
+                "WARNING: code-name '{}' not found in probe_atoms for {}, looking up by code-path/code-line ({})",
+                probe_name, context, format_suggestions(&suggestions)
             );
         }
     }
@@ -459,19 +711,74 @@ fn update_entry_from_atoms(
     let tree = match probe_index.get(code_path) {
         Some(t) => t,
         None => {
-            return Ok((
-                updated,
-                Some(format!("code-path '{}' not found in probe_index", code_path)),
-            ));
+            let suggestions = nearest_names(code_path, probe_index.keys().map(String::as_str), 3);
+            let mut message = format!(
+                "code-path '{}' not found in probe_index ({})",
+                code_path,
+                format_suggestions(&suggestions)
+            );
+            if let Some(moved_to) = suggest_move_target(entry, code_path, probe_atoms) {
+                message.push_str(&format!("; possible move target: '{}'", moved_to));
+            }
+            return Ok((updated, Some(message)));
         }
     };
 
-    let matching_intervals: Vec<_> = tree
+    let exact_matches: Vec<_> = tree
         .query(line_start..line_start + 1)
         .filter(|iv| iv.range.start == line_start)
         .collect();
 
-    if matching_intervals.is_empty() {
+    let (probe_name, matched_start) = if !exact_matches.is_empty() {
+        if exact_matches.len() > 1 {
+            eprintln!(
+                "WARNING: Multiple intervals starting at line {} in {} for {}",
+                line_start, code_path, context
+            );
+        }
+        (exact_matches[0].value.clone(), line_start)
+    } else if let Some((probe_name, start, end)) = find_smallest_enclosing(tree, line_start) {
+        status!(
+            "Note: {} code-line {} falls inside atom '{}' ({}..{}) rather than at its start; anchoring there",
+            context, line_start, probe_name, start, end
+        );
+        (probe_name, start)
+    } else if line_tolerance > 0 {
+        let lo = line_start.saturating_sub(line_tolerance);
+        let hi = line_start + line_tolerance + 1;
+        let mut candidates: Vec<_> = tree
+            .query(lo..hi)
+            .filter(|iv| iv.range.start >= lo && iv.range.start < hi)
+            .collect();
+
+        if candidates.is_empty() {
+            return Ok((
+                updated,
+                Some(format!(
+                    "No interval starting within {} line(s) of {} in {}",
+                    line_tolerance, line_start, code_path
+                )),
+            ));
+        }
+
+        candidates.sort_by(|a, b| {
+            a.range
+                .start
+                .abs_diff(line_start)
+                .cmp(&b.range.start.abs_diff(line_start))
+                .then_with(|| (a.range.end - a.range.start).cmp(&(b.range.end - b.range.start)))
+        });
+
+        let best = &candidates[0];
+        status!(
+            "Re-anchored {}: code-line {} -> {} (drift {})",
+            context,
+            line_start,
+            best.range.start,
+            best.range.start.abs_diff(line_start)
+        );
+        (best.value.clone(), best.range.start)
+    } else {
         return Ok((
             updated,
             Some(format!(
@@ -479,18 +786,13 @@ fn update_entry_from_atoms(
                 line_start, code_path
             )),
         ));
-    }
-
-    if matching_intervals.len() > 1 {
-        eprintln!(
-            "WARNING: Multiple intervals starting at line {} in {} for {}",
-            line_start, code_path, context
-        );
-    }
+    };
 
-    let probe_name = &matching_intervals[0].value;
     if let Some(obj) = updated.as_object_mut() {
         obj.insert("code-name".to_string(), json!(probe_name));
+        if matched_start != line_start {
+            obj.insert("code-line".to_string(), json!(matched_start));
+        }
     }
 
     Ok((updated, None))
@@ -501,13 +803,15 @@ fn sync_structure_json_with_atoms(
     structure: HashMap<String, Value>,
     probe_index: &HashMap<String, IntervalTree<u32, String>>,
     probe_atoms: &HashMap<String, Value>,
+    line_tolerance: u32,
 ) -> Result<HashMap<String, Value>> {
     let mut updated_count = 0;
     let mut not_found_count = 0;
     let mut result = HashMap::new();
 
     for (file_path, entry) in structure {
-        let (updated, error) = update_entry_from_atoms(&entry, probe_index, probe_atoms, &file_path)?;
+        let (updated, error) =
+            update_entry_from_atoms(&entry, probe_index, probe_atoms, &file_path, line_tolerance)?;
 
         if let Some(err) = error {
             eprintln!("WARNING: {} for {}", err, file_path);
@@ -519,8 +823,8 @@ fn sync_structure_json_with_atoms(
         }
     }
 
-    println!("Structure entries updated: {}", updated_count);
-    println!("Not found/skipped: {}", not_found_count);
+    status!("Structure entries updated: {}", updated_count);
+    status!("Not found/skipped: {}", not_found_count);
 
     Ok(result)
 }
@@ -530,6 +834,7 @@ fn sync_structure_files_with_atoms(
     probe_index: &HashMap<String, IntervalTree<u32, String>>,
     probe_atoms: &HashMap<String, Value>,
     structure_root: &Path,
+    line_tolerance: u32,
 ) -> Result<()> {
     let mut updated_count = 0;
     let mut not_found_count = 0;
@@ -549,8 +854,13 @@ fn sync_structure_files_with_atoms(
         };
 
         let entry_value = json!(frontmatter);
-        let (updated, error) =
-            update_entry_from_atoms(&entry_value, probe_index, probe_atoms, &path.display().to_string())?;
+        let (updated, error) = update_entry_from_atoms(
+            &entry_value,
+            probe_index,
+            probe_atoms,
+            &path.display().to_string(),
+            line_tolerance,
+        )?;
 
         if let Some(err) = error {
             eprintln!("WARNING: {} for {}", err, path.display());
@@ -577,17 +887,95 @@ fn sync_structure_files_with_atoms(
         updated_count += 1;
     }
 
-    println!("Structure files updated: {}", updated_count);
-    println!("Not found/skipped: {}", not_found_count);
+    status!("Structure files updated: {}", updated_count);
+    status!("Not found/skipped: {}", not_found_count);
 
     Ok(())
 }
 
+/// `SPDX-License-Identifier:` header line, per the convention rustc's own
+/// `generate-copyright`/`collect-license-metadata` tooling scans for.
+const SPDX_PATTERN: &str = r"SPDX-License-Identifier:\s*(.+)";
+
+/// A copyright notice line, e.g. `Copyright (c) 2024 Jane Doe` or `Copyright © 2024 ...`.
+const COPYRIGHT_PATTERN: &str = r"(?i)copyright\s*(?:\(c\)|\u{a9})?\s*[0-9][0-9,\-\s]*[^\r\n]*";
+
+/// Filenames checked when walking up from a source file looking for an
+/// adjacent license/notice file.
+const LICENSE_FILE_NAMES: &[&str] = &[
+    "LICENSE",
+    "LICENSE.txt",
+    "LICENSE.md",
+    "NOTICE",
+    "NOTICE.txt",
+    "COPYING",
+    "COPYING.txt",
+];
+
+/// Scan `code_path`'s source file for an SPDX identifier and copyright
+/// lines, and walk up from its directory toward `project_root` looking for
+/// a `LICENSE`/`NOTICE`/`COPYING` file, so a copy of third-party source
+/// extracted into `.atom.verilib` still carries its provenance. Best
+/// effort: any piece not found is simply omitted.
+fn scan_license_info(project_root: &Path, code_path: &str) -> Value {
+    let source_file = project_root.join(code_path);
+    let content = std::fs::read_to_string(&source_file).unwrap_or_default();
+
+    let spdx = Regex::new(SPDX_PATTERN)
+        .ok()
+        .and_then(|re| re.captures(&content))
+        .map(|caps| caps[1].trim().to_string());
+
+    let mut seen = std::collections::HashSet::new();
+    let copyright: Vec<String> = Regex::new(COPYRIGHT_PATTERN)
+        .map(|re| {
+            re.find_iter(&content)
+                .map(|m| m.as_str().trim().to_string())
+                .filter(|s| seen.insert(s.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let notice_path = find_adjacent_license_file(project_root, &source_file);
+
+    json!({
+        "spdx": spdx,
+        "copyright": copyright,
+        "notice_path": notice_path,
+    })
+}
+
+/// Walk up from `source_file`'s directory toward `project_root` (inclusive)
+/// looking for a license/notice file, returning its path relative to
+/// `project_root` if one is found.
+fn find_adjacent_license_file(project_root: &Path, source_file: &Path) -> Option<String> {
+    let mut dir = source_file.parent()?;
+
+    loop {
+        for name in LICENSE_FILE_NAMES {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                let relative = candidate.strip_prefix(project_root).unwrap_or(&candidate);
+                return Some(relative.display().to_string());
+            }
+        }
+
+        if dir == project_root {
+            return None;
+        }
+        dir = dir.parent()?;
+    }
+}
+
 /// Generate enriched entry from probe atom data.
-/// Returns a JSON object with code-path, code-lines, code-name, code-module, dependencies, display-name.
+/// Returns a JSON object with code-path, code-lines, code-name, code-module,
+/// dependencies, display-name, code-hash (a content fingerprint of the
+/// atom, used to re-find it if its recorded line drifts), and license (an
+/// SPDX/copyright/notice-path provenance record, see [`scan_license_info`]).
 fn generate_enriched_entry(
     probe_name: &str,
     probe_atoms: &HashMap<String, Value>,
+    project_root: &Path,
 ) -> Result<Option<Value>> {
     let atom = match probe_atoms.get(probe_name) {
         Some(a) => a,
@@ -629,6 +1017,10 @@ fn generate_enriched_entry(
         .and_then(|v| v.as_str())
         .unwrap_or("");
 
+    let content = atom.get("content").and_then(|v| v.as_str()).unwrap_or("");
+    let code_hash = hash_normalized_content(content);
+    let license = scan_license_info(project_root, code_path);
+
     Ok(Some(json!({
         "code-path": code_path,
         "code-lines": {
@@ -639,14 +1031,17 @@ fn generate_enriched_entry(
         "code-module": code_module,
         "dependencies": dependencies,
         "display-name": display_name,
+        "code-hash": code_hash,
+        "license": license,
     })))
 }
 
 /// Enrich structure JSON with atom metadata.
-/// Keys are file paths, values are enriched entries with code-path, code-lines, code-name, code-module, dependencies, display-name.
+/// Keys are file paths, values are enriched entries (see [`generate_enriched_entry`]).
 fn enrich_structure_json(
     structure: &HashMap<String, Value>,
     probe_atoms: &HashMap<String, Value>,
+    project_root: &Path,
 ) -> Result<HashMap<String, Value>> {
     let mut result = HashMap::new();
     let mut enriched_count = 0;
@@ -664,13 +1059,19 @@ fn enrich_structure_json(
             }
         };
 
-        match generate_enriched_entry(probe_name, probe_atoms)? {
+        match generate_enriched_entry(probe_name, probe_atoms, project_root)? {
             Some(enriched_entry) => {
                 result.insert(file_path.clone(), enriched_entry);
                 enriched_count += 1;
             }
             None => {
-                eprintln!("WARNING: Missing atom data for {} ({})", file_path, probe_name);
+                let suggestions = nearest_names(probe_name, probe_atoms.keys().map(String::as_str), 3);
+                eprintln!(
+                    "WARNING: Missing atom data for {} ({}) ({})",
+                    file_path,
+                    probe_name,
+                    format_suggestions(&suggestions)
+                );
                 skipped_count += 1;
                 // Keep original entry if enrichment fails
                 result.insert(file_path.clone(), entry.clone());
@@ -678,18 +1079,180 @@ fn enrich_structure_json(
         }
     }
 
-    println!("Entries enriched: {}", enriched_count);
-    println!("Skipped: {}", skipped_count);
+    status!("Entries enriched: {}", enriched_count);
+    status!("Skipped: {}", skipped_count);
 
     Ok(result)
 }
 
+/// Find the atom in `tree` whose content fingerprint equals `target_hash`,
+/// scanning every interval rather than relying on an exact line position.
+/// Used to re-find an atom whose recorded `code-line` has drifted.
+/// Returns the matching atom's probe name and its current start line.
+fn find_atom_by_content_hash(
+    tree: &IntervalTree<u32, String>,
+    probe_atoms: &HashMap<String, Value>,
+    target_hash: &str,
+) -> Option<(String, u32)> {
+    tree.query(0..u32::MAX).find_map(|iv| {
+        let atom = probe_atoms.get(&iv.value)?;
+        let content = atom.get("content").and_then(|v| v.as_str()).unwrap_or("");
+        (hash_normalized_content(content) == target_hash).then(|| (iv.value.clone(), iv.range.start))
+    })
+}
+
+/// Read the `code-hash` recorded in an existing `.meta.verilib` file, if any.
+fn read_existing_code_hash(meta_file: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(meta_file).ok()?;
+    let meta: Value = serde_json::from_str(&content).ok()?;
+    meta.get("code-hash").and_then(|v| v.as_str()).map(str::to_string)
+}
+
+/// A fenced ` ```rust ` code block pulled from a structure file's markdown
+/// body, with the rustdoc/skeptic-style info-string attributes that
+/// control how its generated test is emitted.
+struct RustCodeBlock {
+    code: String,
+    /// Compile the generated test but don't run it as `#[test]`.
+    no_run: bool,
+    /// Skip the block entirely — not even compiled.
+    ignore: bool,
+    /// Generated test gets `#[should_panic]`.
+    should_panic: bool,
+}
+
+/// Extract skeptic/rustdoc-style fenced ` ```rust ` code blocks from a
+/// markdown body via a `pulldown-cmark` pass, reading the `no_run` /
+/// `ignore` / `should_panic` info-string attributes the same way rustdoc
+/// does for doc-tests.
+fn extract_rust_code_blocks(markdown: &str) -> Vec<RustCodeBlock> {
+    let mut blocks = Vec::new();
+    let mut current: Option<RustCodeBlock> = None;
+
+    for event in MarkdownParser::new(markdown) {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+                let mut tokens = info.split_whitespace();
+                if tokens.next() != Some("rust") {
+                    continue;
+                }
+
+                let mut block = RustCodeBlock {
+                    code: String::new(),
+                    no_run: false,
+                    ignore: false,
+                    should_panic: false,
+                };
+                for token in tokens {
+                    match token {
+                        "no_run" => block.no_run = true,
+                        "ignore" => block.ignore = true,
+                        "should_panic" => block.should_panic = true,
+                        _ => {}
+                    }
+                }
+                current = Some(block);
+            }
+            Event::Text(text) => {
+                if let Some(block) = current.as_mut() {
+                    block.code.push_str(&text);
+                }
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                if let Some(block) = current.take() {
+                    blocks.push(block);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+/// Indent every line of `text` by `prefix`.
+fn indent(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| format!("{}{}\n", prefix, line))
+        .collect()
+}
+
+/// Wrap a snippet in `fn main() { ... }` the way rustdoc does for doc-tests
+/// that are bare statements, unless it already defines its own `fn main`.
+fn wrap_snippet(code: &str) -> String {
+    if code.contains("fn main") {
+        code.to_string()
+    } else {
+        format!("fn main() {{\n{}}}\n", indent(code, "    "))
+    }
+}
+
+/// Parse `path`'s markdown body for fenced ` ```rust ` examples (skeptic
+/// style) and, for each one not marked `ignore`, write a generated test
+/// pairing it with `atom_file`'s extracted content — so CI catches a
+/// documentation example that no longer compiles, or has drifted from the
+/// atom it claims to describe. Written next to the `.atom.verilib` /
+/// `.meta.verilib` outputs as `<stem>.harness.rs`. A no-op if the body has
+/// no `rust` fences.
+fn generate_doc_harness(path: &Path, atom_file: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(path)?;
+    let body_start = content
+        .find("\n---\n")
+        .map(|pos| pos + 5)
+        .and_then(|start| content[start..].find("\n---\n").map(|p| start + p + 5));
+    let body = body_start.map(|start| &content[start..]).unwrap_or("");
+
+    let blocks = extract_rust_code_blocks(body);
+    if blocks.is_empty() {
+        return Ok(());
+    }
+
+    let atom_file_name = atom_file.file_name().and_then(|f| f.to_str()).unwrap_or("");
+
+    let mut harness = String::new();
+    harness.push_str("// Auto-generated by `atomize --generate-harness`; do not edit by hand.\n");
+    harness.push_str(&format!(
+        "// Documented examples from {}, paired with {}.\n\n",
+        path.display(),
+        atom_file.display()
+    ));
+
+    for (i, block) in blocks.iter().enumerate() {
+        if block.ignore {
+            harness.push_str(&format!("// Block {} skipped (fence marked `ignore`)\n\n", i));
+            continue;
+        }
+
+        harness.push_str(&format!("mod doc_example_{} {{\n", i));
+        harness.push_str(&indent(&wrap_snippet(&block.code), "    "));
+        harness.push_str("}\n\n");
+
+        if !block.no_run {
+            harness.push_str("#[test]\n");
+        }
+        if block.should_panic {
+            harness.push_str("#[should_panic]\n");
+        }
+        harness.push_str(&format!("fn doc_example_{}_matches_atom() {{\n", i));
+        harness.push_str(&format!("    let atom = include_str!({:?});\n", atom_file_name));
+        harness.push_str(&format!("    let documented = {:?};\n", block.code.trim()));
+        harness.push_str(
+            "    assert_eq!(documented.trim(), atom.trim(), \"documentation example drifted from the atom it describes\");\n",
+        );
+        harness.push_str("}\n\n");
+    }
+
+    std::fs::write(path.with_extension("harness.rs"), harness)?;
+    Ok(())
+}
+
 /// Generate metadata files for each structure .md file.
 fn populate_structure_files_metadata(
     probe_atoms: &HashMap<String, Value>,
     probe_index: &HashMap<String, IntervalTree<u32, String>>,
     structure_root: &Path,
     project_root: &Path,
+    generate_harness: bool,
 ) -> Result<()> {
     let mut created_count = 0;
     let mut skipped_count = 0;
@@ -711,44 +1274,71 @@ fn populate_structure_files_metadata(
             }
         };
 
+        let probe_frontmatter: ProbeFrontmatter = frontmatter_to_typed(&frontmatter)
+            .with_context(|| format!("Invalid frontmatter in {}", path.display()))?;
+
+        for include in &probe_frontmatter.includes {
+            if !probe_atoms.contains_key(include) {
+                bail!(
+                    "{}: includes '{}' does not resolve to any atom",
+                    path.display(),
+                    include
+                );
+            }
+        }
+
+        let existing_code_hash = read_existing_code_hash(&path.with_extension("meta.verilib"));
+
+        let cp = probe_frontmatter.code_path.as_str();
+        let ls = probe_frontmatter.code_line;
+
         // Try to get code-name from frontmatter, or look it up from probe_index
-        let probe_name: String = match frontmatter.get("code-name").and_then(|v| v.as_str()) {
-            Some(name) => name.to_string(),
+        let probe_name: String = match probe_frontmatter.code_name.clone() {
+            Some(name) => name,
             None => {
-                // Look up code-name from probe_index using code-path and code-line
-                let code_path = frontmatter.get("code-path").and_then(|v| v.as_str());
-                let line_start = frontmatter.get("code-line").and_then(|v| v.as_u64()).map(|l| l as u32);
-
-                match (code_path, line_start) {
-                    (Some(cp), Some(ls)) => {
-                        if let Some(tree) = probe_index.get(cp) {
-                            let matching: Vec<_> = tree
-                                .query(ls..ls + 1)
-                                .filter(|iv| iv.range.start == ls)
-                                .collect();
-                            if !matching.is_empty() {
-                                matching[0].value.clone()
-                            } else {
-                                eprintln!("WARNING: No atom found at {}:{} for {}", cp, ls, path.display());
-                                skipped_count += 1;
-                                continue;
-                            }
-                        } else {
-                            eprintln!("WARNING: code-path '{}' not in probe_index for {}", cp, path.display());
-                            skipped_count += 1;
-                            continue;
-                        }
-                    }
-                    _ => {
-                        eprintln!("WARNING: Missing code-name and code-path/code-line for {}", path.display());
+                if let Some(tree) = probe_index.get(cp) {
+                    let matching: Vec<_> = tree
+                        .query(ls..ls + 1)
+                        .filter(|iv| iv.range.start == ls)
+                        .collect();
+                    if !matching.is_empty() {
+                        matching[0].value.clone()
+                    } else if let Some((probe_name, start, end)) = find_smallest_enclosing(tree, ls) {
+                        status!(
+                            "Note: {} code-line {} falls inside atom '{}' ({}..{}) rather than at its start",
+                            path.display(),
+                            ls,
+                            probe_name,
+                            start,
+                            end
+                        );
+                        probe_name
+                    } else if let Some((probe_name, new_line)) = existing_code_hash
+                        .as_deref()
+                        .and_then(|hash| find_atom_by_content_hash(tree, probe_atoms, hash))
+                    {
+                        status!(
+                            "Re-anchored {} via code-hash: code-line {} -> {} (code-name '{}')",
+                            path.display(),
+                            ls,
+                            new_line,
+                            probe_name
+                        );
+                        probe_name
+                    } else {
+                        eprintln!("WARNING: No atom found at {}:{} for {}", cp, ls, path.display());
                         skipped_count += 1;
                         continue;
                     }
+                } else {
+                    eprintln!("WARNING: code-path '{}' not in probe_index for {}", cp, path.display());
+                    skipped_count += 1;
+                    continue;
                 }
             }
         };
 
-        let meta_data = match generate_enriched_entry(&probe_name, probe_atoms)? {
+        let mut meta_data = match generate_enriched_entry(&probe_name, probe_atoms, project_root)? {
             Some(md) => md,
             None => {
                 eprintln!("WARNING: Missing code-path or line info for {}", path.display());
@@ -757,6 +1347,18 @@ fn populate_structure_files_metadata(
             }
         };
 
+        if let Some(obj) = meta_data.as_object_mut() {
+            obj.insert("features".to_string(), json!(probe_frontmatter.features));
+            obj.insert("includes".to_string(), json!(probe_frontmatter.includes));
+            obj.insert("flags".to_string(), json!(probe_frontmatter.flags));
+            if let Some(negative) = &probe_frontmatter.negative {
+                obj.insert(
+                    "negative".to_string(),
+                    json!({ "phase": negative.phase, "error_type": negative.error_type }),
+                );
+            }
+        }
+
         // Write metadata file
         let meta_file = path.with_extension("meta.verilib");
         let content = serde_json::to_string_pretty(&meta_data)?;
@@ -789,6 +1391,10 @@ fn populate_structure_files_metadata(
 
                 let atom_file = path.with_extension("atom.verilib");
                 std::fs::write(&atom_file, atom_content)?;
+
+                if generate_harness {
+                    generate_doc_harness(path, &atom_file)?;
+                }
             }
         } else {
             eprintln!("WARNING: Source file not found: {}", source_file.display());
@@ -797,8 +1403,90 @@ fn populate_structure_files_metadata(
         created_count += 1;
     }
 
-    println!("Metadata files created: {}", created_count);
-    println!("Skipped: {}", skipped_count);
+    status!("Metadata files created: {}", created_count);
+    status!("Skipped: {}", skipped_count);
+
+    Ok(())
+}
+
+/// Report, without writing anything, which structure files' `code-line` is
+/// still exact, which has drifted but can be re-anchored via `code-hash`,
+/// and which is genuinely gone (no atom in the file matches the recorded
+/// hash). Returns an error if anything needs attention, so CI can fail the
+/// build the same way `fmt --check` does.
+fn check_structure_files_drift(
+    probe_atoms: &HashMap<String, Value>,
+    probe_index: &HashMap<String, IntervalTree<u32, String>>,
+    structure_root: &Path,
+) -> Result<()> {
+    let mut fresh_count = 0;
+    let mut drifted_count = 0;
+    let mut missing_count = 0;
+
+    for entry in walkdir::WalkDir::new(structure_root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !path.extension().map_or(false, |ext| ext == "md") {
+            continue;
+        }
+
+        let Ok(frontmatter) = parse_frontmatter(path) else {
+            continue;
+        };
+
+        let code_path = frontmatter.get("code-path").and_then(|v| v.as_str());
+        let line_start = frontmatter.get("code-line").and_then(|v| v.as_u64()).map(|l| l as u32);
+        let (Some(cp), Some(ls)) = (code_path, line_start) else {
+            continue;
+        };
+
+        let Some(tree) = probe_index.get(cp) else {
+            status!("MISSING  {} ({}:{} — code-path not in probe_index)", path.display(), cp, ls);
+            missing_count += 1;
+            continue;
+        };
+
+        if tree.query(ls..ls + 1).any(|iv| iv.range.start == ls) {
+            status!("FRESH    {}", path.display());
+            fresh_count += 1;
+            continue;
+        }
+
+        let existing_code_hash = read_existing_code_hash(&path.with_extension("meta.verilib"));
+        match existing_code_hash
+            .as_deref()
+            .and_then(|hash| find_atom_by_content_hash(tree, probe_atoms, hash))
+        {
+            Some((probe_name, new_line)) => {
+                status!(
+                    "DRIFTED  {} ({}:{} -> {}:{}, code-name '{}')",
+                    path.display(),
+                    cp,
+                    ls,
+                    cp,
+                    new_line,
+                    probe_name
+                );
+                drifted_count += 1;
+            }
+            None => {
+                status!("MISSING  {} ({}:{} — no atom with matching content found)", path.display(), cp, ls);
+                missing_count += 1;
+            }
+        }
+    }
+
+    status!();
+    status!(
+        "Fresh: {}, Drifted: {}, Missing: {}",
+        fresh_count, drifted_count, missing_count
+    );
+
+    if drifted_count > 0 || missing_count > 0 {
+        bail!("{} structure file(s) need attention (drifted or missing)", drifted_count + missing_count);
+    }
 
     Ok(())
 }
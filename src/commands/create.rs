@@ -3,7 +3,13 @@
 //! Initialize structure files from source analysis.
 
 use crate::config::Config;
-use crate::utils::{check_leanblueprint_installed, parse_github_link, run_command, write_frontmatter_file};
+use crate::coverage::{self, ReportFormat};
+use crate::search_index;
+use crate::site;
+use crate::utils::{
+    check_leanblueprint_installed, parse_github_link, run_command, status, write_files_parallel,
+    write_frontmatter_file,
+};
 use crate::{StructureForm, StructureType};
 use anyhow::{bail, Context, Result};
 use regex::Regex;
@@ -18,6 +24,9 @@ pub fn run(
     structure_type: StructureType,
     form: StructureForm,
     root: Option<PathBuf>,
+    report: Option<ReportFormat>,
+    html: Option<PathBuf>,
+    dot: Option<PathBuf>,
 ) -> Result<()> {
     let project_root = project_root.canonicalize()
         .context("Failed to resolve project root")?;
@@ -69,10 +78,10 @@ pub fn run(
     // Write config file
     let config = Config::new(structure_type, form, &structure_root_relative);
     let config_path = config.save(&project_root)?;
-    println!("Wrote config to {}", config_path.display());
+    status!("Wrote config to {}", config_path.display());
 
     // Generate structure output
-    println!("\nGenerating structure output...");
+    status!("\nGenerating structure output...");
     match form {
         StructureForm::Json => {
             generate_structure_json(&structure, &structure_json_path)?;
@@ -83,6 +92,23 @@ pub fn run(
         }
     }
 
+    let search_index_path = search_index::write_search_index(&structure, structure_type, &verilib_path)?;
+    status!("Wrote search index to {}", search_index_path.display());
+
+    if let Some(format) = report {
+        let report_path = coverage::write_report(&structure, structure_type, format, &verilib_path)?;
+        status!("Wrote coverage report to {}", report_path.display());
+    }
+
+    if let Some(html_root) = html {
+        status!("\nGenerating HTML site...");
+        site::generate_structure_html(&structure, structure_type, &html_root)?;
+    }
+
+    if let Some(dot_path) = dot {
+        generate_structure_dot(&structure, structure_type, &dot_path)?;
+    }
+
     Ok(())
 }
 
@@ -92,17 +118,11 @@ pub fn run(
 
 /// Run 'leanblueprint web' to generate the blueprint/web folder.
 fn run_leanblueprint_web(project_root: &Path) -> Result<()> {
-    println!("Running 'leanblueprint web' to generate blueprint...");
+    status!("Running 'leanblueprint web' to generate blueprint...");
 
-    let output = run_command("leanblueprint", &["web"], Some(project_root))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        eprintln!("Error running leanblueprint web:\n{}", stderr);
-        bail!("leanblueprint web failed");
-    }
+    run_command("leanblueprint", &["web"], Some(project_root))?;
 
-    println!("Successfully generated blueprint/web");
+    status!("Successfully generated blueprint/web");
     Ok(())
 }
 
@@ -120,15 +140,15 @@ fn generate_blueprint_json(
         bail!("{} not found", html_path.display());
     }
 
-    println!("Parsing {}...", html_path.display());
+    status!("Parsing {}...", html_path.display());
     let html_content = std::fs::read_to_string(&html_path)?;
     let document = Html::parse_document(&html_content);
 
     let node_info = get_node_info(&document)?;
-    println!("Found {} dep-modal-container elements", node_info.len());
+    status!("Found {} dep-modal-container elements", node_info.len());
 
     let nodes = get_dep_graph(&document, &html_content, &node_info)?;
-    println!("Parsed {} nodes from dependency graph", nodes.len());
+    status!("Parsed {} nodes from dependency graph", nodes.len());
 
     if let Some(parent) = output_path.parent() {
         std::fs::create_dir_all(parent)?;
@@ -136,7 +156,7 @@ fn generate_blueprint_json(
 
     let content = serde_json::to_string_pretty(&nodes)?;
     std::fs::write(output_path, content)?;
-    println!("Wrote blueprint data to {}", output_path.display());
+    status!("Wrote blueprint data to {}", output_path.display());
 
     Ok(nodes)
 }
@@ -366,33 +386,38 @@ fn blueprint_to_structure(blueprint_data: &HashMap<String, Value>) -> HashMap<St
     for (blueprint_id, attributes) in blueprint_data {
         let file_path = format!("{}.md", blueprint_id);
 
-        let mut all_deps = Vec::new();
-        if let Some(type_deps) = attributes.get("type-dependencies").and_then(|v| v.as_array()) {
-            for dep in type_deps {
-                if let Some(s) = dep.as_str() {
-                    all_deps.push(format!("veri:{}", s));
-                }
-            }
-        }
-        if let Some(term_deps) = attributes.get("term-dependencies").and_then(|v| v.as_array()) {
-            for dep in term_deps {
-                if let Some(s) = dep.as_str() {
-                    all_deps.push(format!("veri:{}", s));
-                }
-            }
-        }
+        let type_deps: Vec<String> = attributes
+            .get("type-dependencies")
+            .and_then(|v| v.as_array())
+            .map(|deps| deps.iter().filter_map(|d| d.as_str()).map(|s| format!("veri:{}", s)).collect())
+            .unwrap_or_default();
+        let term_deps: Vec<String> = attributes
+            .get("term-dependencies")
+            .and_then(|v| v.as_array())
+            .map(|deps| deps.iter().filter_map(|d| d.as_str()).map(|s| format!("veri:{}", s)).collect())
+            .unwrap_or_default();
+        let all_deps: Vec<String> = type_deps.iter().chain(term_deps.iter()).cloned().collect();
 
         let content = attributes
             .get("content")
             .and_then(|v| v.as_str())
             .unwrap_or("");
 
+        let kind = attributes.get("kind").and_then(|v| v.as_str()).unwrap_or("");
+        let type_status = attributes.get("type-status").and_then(|v| v.as_str()).unwrap_or("unknown");
+        let term_status = attributes.get("term-status").and_then(|v| v.as_str()).unwrap_or("unknown");
+
         result.insert(
             file_path,
             json!({
                 "veri-name": format!("veri:{}", blueprint_id),
                 "dependencies": all_deps,
+                "type-dependencies": type_deps,
+                "term-dependencies": term_deps,
                 "content": content,
+                "kind": kind,
+                "type-status": type_status,
+                "term-status": term_status,
             }),
         );
     }
@@ -415,7 +440,7 @@ fn run_analyze_verus_specs_proofs(
         bail!("Script not found: {}", script_path.display());
     }
 
-    println!("Running analyze_verus_specs_proofs.py...");
+    status!("Running analyze_verus_specs_proofs.py...");
 
     let seed_relative = seed_path
         .strip_prefix(project_root)
@@ -429,7 +454,7 @@ fn run_analyze_verus_specs_proofs(
         std::fs::create_dir_all(parent)?;
     }
 
-    let output = run_command(
+    run_command(
         "uv",
         &[
             "run",
@@ -442,13 +467,7 @@ fn run_analyze_verus_specs_proofs(
         Some(project_root),
     )?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        eprintln!("Error running analyze_verus_specs_proofs.py:\n{}", stderr);
-        bail!("analyze_verus_specs_proofs.py failed");
-    }
-
-    println!("Generated tracked functions CSV at {}", output_path.display());
+    status!("Generated tracked functions CSV at {}", output_path.display());
     Ok(())
 }
 
@@ -561,6 +580,9 @@ fn tracked_to_structure(tracked: &HashMap<String, TrackedFunction>) -> HashMap<S
                     "code-line": line_start,
                     "code-path": code_path,
                     "code-name": null,
+                    "has-spec": func.has_spec,
+                    "has-proof": func.has_proof,
+                    "is-external-body": func.is_external_body,
                 }),
             );
         }
@@ -573,16 +595,21 @@ fn tracked_to_structure(tracked: &HashMap<String, TrackedFunction>) -> HashMap<S
 // Output generation
 // =============================================================================
 
-/// Generate structure .md files from a structure dictionary.
+/// Generate structure .md files from a structure dictionary. Writes are
+/// fanned out across a worker pool since each entry is independent; see
+/// `write_files_parallel` for how "already exists" warnings stay
+/// deterministic despite that.
 fn generate_structure_files(structure: &HashMap<String, Value>, structure_root: &Path) -> Result<()> {
-    let mut created_count = 0;
+    let items: Vec<(String, Value)> = structure.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
 
-    for (relative_path_str, metadata) in structure {
+    let created_count = write_files_parallel(items, |relative_path_str, metadata| {
         let file_path = structure_root.join(relative_path_str);
 
-        if file_path.exists() {
-            eprintln!("WARNING: File already exists, overwriting: {}", file_path.display());
-        }
+        let warning = if file_path.exists() {
+            Some(format!("WARNING: File already exists, overwriting: {}", file_path.display()))
+        } else {
+            None
+        };
 
         let mut metadata_map: HashMap<String, Value> = if let Some(obj) = metadata.as_object() {
             obj.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
@@ -594,10 +621,10 @@ fn generate_structure_files(structure: &HashMap<String, Value>, structure_root:
         let body = body_content.as_ref().and_then(|v| v.as_str());
 
         write_frontmatter_file(&file_path, &metadata_map, body)?;
-        created_count += 1;
-    }
+        Ok(warning)
+    })?;
 
-    println!("Created {} structure files in {}", created_count, structure_root.display());
+    status!("Created {} structure files in {}", created_count, structure_root.display());
     Ok(())
 }
 
@@ -609,7 +636,136 @@ fn generate_structure_json(structure: &HashMap<String, Value>, output_path: &Pat
 
     let content = serde_json::to_string_pretty(structure)?;
     std::fs::write(output_path, content)?;
-    println!("Wrote structure to {}", output_path.display());
+    status!("Wrote structure to {}", output_path.display());
+
+    Ok(())
+}
+
+/// Map a `kind` back to the DOT shape `parse_node_element` derives it
+/// from (`"theorem"` <- `ellipse`, `"definition"` <- `box`).
+fn kind_to_shape(kind: Option<&str>) -> Option<&'static str> {
+    match kind? {
+        "theorem" => Some("ellipse"),
+        "definition" => Some("box"),
+        _ => None,
+    }
+}
+
+/// Map a `type-status` back to the DOT `color` `parse_node_element`
+/// derives it from.
+fn type_status_to_color(status: Option<&str>) -> Option<&'static str> {
+    match status? {
+        "stated" => Some("green"),
+        "can-state" => Some("blue"),
+        "not-ready" => Some("\"#FFAA33\""),
+        "mathlib" => Some("darkgreen"),
+        _ => None,
+    }
+}
+
+/// Map a `term-status` back to the DOT `fillcolor` `parse_node_element`
+/// derives it from.
+fn term_status_to_color(status: Option<&str>) -> Option<&'static str> {
+    match status? {
+        "proved" => Some("\"#9CEC8B\""),
+        "defined" => Some("\"#B0ECA3\""),
+        "can-prove" => Some("\"#A3D6FF\""),
+        "fully-proved" => Some("\"#1CAC78\""),
+        _ => None,
+    }
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('"', "\\\"")
+}
+
+/// Render `structure` as a `strict digraph` DOT source: the exact
+/// inverse of `parse_node_element`/`get_dep_graph`'s ingestion. Node
+/// shape comes from `kind`, node/fill color from `type-status`/
+/// `term-status`, and edges are dashed for `type-dependencies`, solid for
+/// `term-dependencies`. Structures with no type/term split (dalek-lite,
+/// once `atomize` has populated a flat `dependencies` list) fall back to
+/// solid edges and unstyled nodes.
+fn structure_to_dot(structure: &HashMap<String, Value>, structure_type: StructureType) -> String {
+    let name_field = match structure_type {
+        StructureType::Blueprint => "veri-name",
+        StructureType::DalekLite => "code-name",
+    };
+
+    let node_name = |file_path: &str, entry: &Value| -> String {
+        entry
+            .get(name_field)
+            .and_then(|v| v.as_str())
+            .unwrap_or(file_path)
+            .to_string()
+    };
+
+    let mut dot = String::from("strict digraph \"\" {\n");
+
+    for (file_path, entry) in structure {
+        let name = node_name(file_path, entry);
+        let mut attrs = Vec::new();
+
+        if let Some(shape) = kind_to_shape(entry.get("kind").and_then(|v| v.as_str())) {
+            attrs.push(format!("shape={}", shape));
+        }
+        if let Some(color) = type_status_to_color(entry.get("type-status").and_then(|v| v.as_str())) {
+            attrs.push(format!("color={}", color));
+        }
+        if let Some(fillcolor) = term_status_to_color(entry.get("term-status").and_then(|v| v.as_str())) {
+            attrs.push(format!("fillcolor={}", fillcolor));
+        }
+
+        if attrs.is_empty() {
+            dot.push_str(&format!("  \"{}\";\n", escape_dot(&name)));
+        } else {
+            dot.push_str(&format!("  \"{}\" [{}];\n", escape_dot(&name), attrs.join(", ")));
+        }
+    }
+
+    for (file_path, entry) in structure {
+        let source = node_name(file_path, entry);
+        let has_split =
+            entry.get("type-dependencies").is_some() || entry.get("term-dependencies").is_some();
+
+        if has_split {
+            if let Some(type_deps) = entry.get("type-dependencies").and_then(|v| v.as_array()) {
+                for dep in type_deps.iter().filter_map(|d| d.as_str()) {
+                    dot.push_str(&format!(
+                        "  \"{}\" -> \"{}\" [style=dashed];\n",
+                        escape_dot(&source),
+                        escape_dot(dep)
+                    ));
+                }
+            }
+            if let Some(term_deps) = entry.get("term-dependencies").and_then(|v| v.as_array()) {
+                for dep in term_deps.iter().filter_map(|d| d.as_str()) {
+                    dot.push_str(&format!(
+                        "  \"{}\" -> \"{}\";\n",
+                        escape_dot(&source),
+                        escape_dot(dep)
+                    ));
+                }
+            }
+        } else if let Some(deps) = entry.get("dependencies").and_then(|v| v.as_array()) {
+            for dep in deps.iter().filter_map(|d| d.as_str()) {
+                dot.push_str(&format!("  \"{}\" -> \"{}\";\n", escape_dot(&source), escape_dot(dep)));
+            }
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Write `structure` as a DOT file.
+fn generate_structure_dot(structure: &HashMap<String, Value>, structure_type: StructureType, output_path: &Path) -> Result<()> {
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(output_path, structure_to_dot(structure, structure_type))?;
+    status!("Wrote dependency graph to {}", output_path.display());
 
     Ok(())
 }
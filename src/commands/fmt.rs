@@ -0,0 +1,143 @@
+//! Fmt subcommand implementation.
+//!
+//! Canonicalize structure files — JSON via `serde_json`, markdown
+//! frontmatter via `serde_yaml` — so re-running `fmt` is always a no-op and
+//! hand-edited files converge to the same form the tool writes.
+
+use crate::config::ConfigPaths;
+use crate::utils::{parse_frontmatter, render_frontmatter, status};
+use crate::StructureForm;
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Run the fmt subcommand.
+///
+/// In `check` mode no files are rewritten; instead every file that isn't
+/// already canonical is reported and the command returns an error so CI
+/// can fail the build.
+pub fn run(project_root: PathBuf, check: bool) -> Result<()> {
+    let project_root = project_root
+        .canonicalize()
+        .context("Failed to resolve project root")?;
+    let config = ConfigPaths::load(&project_root)?;
+    let structure_form = config.config.get_structure_form()?;
+
+    let mut dirty = Vec::new();
+
+    match structure_form {
+        StructureForm::Json => {
+            if config.structure_json_path.exists() {
+                if let Some(path) = fmt_json_file(&config.structure_json_path, check)? {
+                    dirty.push(path);
+                }
+            }
+        }
+        StructureForm::Files => {
+            if config.structure_root.exists() {
+                for entry in walkdir::WalkDir::new(&config.structure_root)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                {
+                    let path = entry.path();
+                    if path.extension().map_or(false, |ext| ext == "md") {
+                        if let Some(path) = fmt_frontmatter_file(path, check)? {
+                            dirty.push(path);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if dirty.is_empty() {
+        status!("All structure files are already canonical.");
+        return Ok(());
+    }
+
+    if check {
+        status!("{} file(s) are not canonical:", dirty.len());
+        for path in &dirty {
+            status!("  {}", path.display());
+        }
+        bail!("{} structure file(s) need `fmt`", dirty.len());
+    }
+
+    status!("Formatted {} file(s):", dirty.len());
+    for path in &dirty {
+        status!("  {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Canonicalize a JSON structure file in place. Returns the path if it
+/// wasn't already canonical; in `check` mode nothing is written.
+fn fmt_json_file(path: &Path, check: bool) -> Result<Option<PathBuf>> {
+    let original = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let structure: BTreeMap<String, Value> = serde_json::from_str(&original)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    let canonical = format!("{}\n", serde_json::to_string_pretty(&structure)?);
+
+    if canonical == original {
+        return Ok(None);
+    }
+
+    if !check {
+        std::fs::write(path, &canonical)?;
+    }
+
+    Ok(Some(path.to_path_buf()))
+}
+
+/// Canonicalize a markdown file's YAML frontmatter in place, keeping its
+/// body apart from leading/trailing blank lines, which are dropped, and
+/// line endings, which become `\n`. Returns the path if it wasn't already canonical; in
+/// `check` mode nothing is written.
+fn fmt_frontmatter_file(path: &Path, check: bool) -> Result<Option<PathBuf>> {
+    let original = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let metadata = parse_frontmatter(path)?;
+    let body = split_body(&original);
+    let canonical = render_frontmatter(&metadata, body.as_deref())?;
+
+    if canonical == original {
+        return Ok(None);
+    }
+
+    if !check {
+        std::fs::write(path, &canonical)?;
+    }
+
+    Ok(Some(path.to_path_buf()))
+}
+
+/// Extract the body following a frontmatter block's closing `---`, the
+/// same way `parse_frontmatter` extracts the block preceding it.
+fn split_body(content: &str) -> Option<String> {
+    let mut lines = content.lines();
+    if lines.next()? != "---" {
+        return None;
+    }
+
+    let mut closed = false;
+    for line in lines.by_ref() {
+        if line == "---" {
+            closed = true;
+            break;
+        }
+    }
+    if !closed {
+        return None;
+    }
+
+    let body = lines.collect::<Vec<_>>().join("\n");
+    let body = body.trim_start_matches('\n').trim_end_matches('\n');
+    if body.is_empty() {
+        None
+    } else {
+        Some(body.to_string())
+    }
+}
@@ -0,0 +1,8 @@
+//! Subcommand implementations.
+
+pub mod atomize;
+pub mod create;
+pub mod fmt;
+pub mod search;
+pub mod specify;
+pub mod verify;
@@ -0,0 +1,334 @@
+//! Search subcommand implementation.
+//!
+//! Full-text search over enriched structure entries (`display-name`,
+//! `code-module`, `code-path`, `code-name`, `dependencies`, and the atom's
+//! source content). Builds a one-shot in-memory inverted index with
+//! identifier-aware tokenization, prefix matching, and typo tolerance — no
+//! index is persisted, so a search always reflects the structure files on
+//! disk at the time it's run.
+
+use crate::config::ConfigPaths;
+use crate::utils::{get_structure_entries, levenshtein, status};
+use crate::StructureForm;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Per-field weight applied to a token's occurrences when scoring a query
+/// match against an atom, so a hit on its name counts for more than an
+/// incidental match somewhere in its body.
+const FIELD_WEIGHT_DISPLAY_NAME: f64 = 5.0;
+const FIELD_WEIGHT_CODE_NAME: f64 = 4.0;
+const FIELD_WEIGHT_CODE_MODULE: f64 = 3.0;
+const FIELD_WEIGHT_CODE_PATH: f64 = 2.0;
+const FIELD_WEIGHT_DEPENDENCIES: f64 = 2.0;
+const FIELD_WEIGHT_CONTENT: f64 = 1.0;
+
+/// Score multiplier for how a query word matched an index token: an exact
+/// match counts in full, a prefix match partially, and a typo-tolerant
+/// fuzzy match least, so exact hits always outrank fuzzy ones.
+const MATCH_QUALITY_EXACT: f64 = 1.0;
+const MATCH_QUALITY_PREFIX: f64 = 0.6;
+const MATCH_QUALITY_FUZZY: f64 = 0.3;
+
+/// Only try fuzzy (Levenshtein) matching for query words at least this
+/// long; shorter words have too many near-neighbors for edit-distance-2 to
+/// mean anything (e.g. "cat" is within 2 of dozens of unrelated tokens).
+const FUZZY_MIN_QUERY_LEN: usize = 5;
+const FUZZY_MAX_DISTANCE: usize = 2;
+
+const DEFAULT_RESULT_LIMIT: usize = 20;
+
+/// One postings entry: `probe_name` has a token occurrence in a field
+/// weighted `field_weight`.
+struct Posting {
+    probe_name: String,
+    field_weight: f64,
+}
+
+/// Everything about an atom a hit needs to render, resolved once up front
+/// rather than re-derived per query.
+struct AtomInfo {
+    probe_name: String,
+    file_path: String,
+    display_name: String,
+    code_path: String,
+    code_line: u32,
+}
+
+/// An in-memory inverted index over a project's structure entries.
+struct SearchIndex {
+    postings: HashMap<String, Vec<Posting>>,
+    atoms: HashMap<String, AtomInfo>,
+}
+
+impl SearchIndex {
+    /// Score every atom matching `query` and return hits sorted by
+    /// descending score (ties broken by `probe_name` for determinism).
+    fn search(&self, query: &str) -> Vec<(&AtomInfo, f64)> {
+        let mut scores: HashMap<&str, f64> = HashMap::new();
+
+        for word in tokenize(query) {
+            for (token, postings) in &self.postings {
+                let quality = if *token == word {
+                    MATCH_QUALITY_EXACT
+                } else if token.starts_with(&word) {
+                    MATCH_QUALITY_PREFIX
+                } else if word.len() >= FUZZY_MIN_QUERY_LEN
+                    && levenshtein(token, &word) <= FUZZY_MAX_DISTANCE
+                {
+                    MATCH_QUALITY_FUZZY
+                } else {
+                    continue;
+                };
+
+                for posting in postings {
+                    *scores.entry(posting.probe_name.as_str()).or_insert(0.0) +=
+                        posting.field_weight * quality;
+                }
+            }
+        }
+
+        let mut hits: Vec<(&AtomInfo, f64)> = scores
+            .into_iter()
+            .filter_map(|(probe_name, score)| self.atoms.get(probe_name).map(|atom| (atom, score)))
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.probe_name.cmp(&b.0.probe_name))
+        });
+
+        hits
+    }
+}
+
+/// Run the search subcommand.
+pub fn run(project_root: PathBuf, query: String, limit: Option<usize>) -> Result<()> {
+    let project_root = project_root
+        .canonicalize()
+        .context("Failed to resolve project root")?;
+    let config = ConfigPaths::load(&project_root)?;
+    let structure_type = config.config.get_structure_type()?;
+    let structure_form = config.config.get_structure_form()?;
+
+    let entries = get_structure_entries(
+        structure_type,
+        structure_form,
+        &config.structure_root,
+        &config.structure_json_path,
+    )?;
+
+    if entries.is_empty() {
+        status!("No structure entries found; run `create` and `atomize` first.");
+        return Ok(());
+    }
+
+    let index = build_index(&project_root, structure_form, entries);
+    let hits = index.search(&query);
+
+    if hits.is_empty() {
+        status!("No matches for '{}'.", query);
+        return Ok(());
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_RESULT_LIMIT).max(1);
+    for (atom, score) in hits.into_iter().take(limit) {
+        status!(
+            "{:>6.2}  {:<40} {}  {}:{}",
+            score,
+            atom.display_name,
+            atom.probe_name,
+            atom.code_path,
+            atom.code_line
+        );
+        status!("        {}", atom.file_path);
+    }
+
+    Ok(())
+}
+
+/// Build the inverted index and per-atom render info from `entries`.
+fn build_index(
+    project_root: &Path,
+    structure_form: StructureForm,
+    entries: HashMap<String, Value>,
+) -> SearchIndex {
+    let mut postings: HashMap<String, Vec<Posting>> = HashMap::new();
+    let mut atoms = HashMap::new();
+
+    for (probe_name, entry) in entries {
+        let display_name = entry
+            .get("display-name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        let code_module = entry.get("code-module").and_then(|v| v.as_str()).unwrap_or("");
+        let code_path = entry
+            .get("code-path")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        let dependencies: Vec<String> = entry
+            .get("dependencies")
+            .and_then(|v| v.as_array())
+            .map(|deps| deps.iter().filter_map(|d| d.as_str().map(str::to_string)).collect())
+            .unwrap_or_default();
+        let file_path = entry
+            .get("__file_path")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        let code_line = entry_code_line(&entry);
+        let content = entry_content(project_root, structure_form, &entry, &file_path);
+
+        let mut index_field = |text: &str, weight: f64| {
+            for token in tokenize(text) {
+                postings.entry(token).or_default().push(Posting {
+                    probe_name: probe_name.clone(),
+                    field_weight: weight,
+                });
+            }
+        };
+
+        index_field(&display_name, FIELD_WEIGHT_DISPLAY_NAME);
+        index_field(&probe_name, FIELD_WEIGHT_CODE_NAME);
+        index_field(code_module, FIELD_WEIGHT_CODE_MODULE);
+        index_field(&code_path, FIELD_WEIGHT_CODE_PATH);
+        for dep in &dependencies {
+            index_field(dep, FIELD_WEIGHT_DEPENDENCIES);
+        }
+        index_field(&content, FIELD_WEIGHT_CONTENT);
+
+        atoms.insert(
+            probe_name.clone(),
+            AtomInfo {
+                probe_name,
+                file_path,
+                display_name,
+                code_path,
+                code_line,
+            },
+        );
+    }
+
+    SearchIndex { postings, atoms }
+}
+
+/// Read an entry's starting line, from either the enriched `code-lines`
+/// object (`atomize` output) or the raw singular `code-line` field.
+fn entry_code_line(entry: &Value) -> u32 {
+    if let Some(line) = entry.get("code-line").and_then(|v| v.as_u64()) {
+        return line as u32;
+    }
+    entry
+        .get("code-lines")
+        .and_then(|lines| lines.get("start"))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as u32
+}
+
+/// Get the atom's source content: for the `Files` form, from its sibling
+/// `.atom.verilib` file; for the `Json` form, by slicing `code-path` at
+/// `code-lines` directly, since that form has no extracted-content file.
+fn entry_content(project_root: &Path, structure_form: StructureForm, entry: &Value, file_path: &str) -> String {
+    match structure_form {
+        StructureForm::Files => {
+            let atom_file = Path::new(file_path).with_extension("atom.verilib");
+            std::fs::read_to_string(atom_file).unwrap_or_default()
+        }
+        StructureForm::Json => {
+            let code_path = entry.get("code-path").and_then(|v| v.as_str());
+            let lines = entry.get("code-lines");
+            let start = lines.and_then(|l| l.get("start")).and_then(|v| v.as_u64());
+            let end = lines.and_then(|l| l.get("end")).and_then(|v| v.as_u64());
+            match (code_path, start, end) {
+                (Some(code_path), Some(start), Some(end)) => {
+                    read_source_region(project_root, code_path, start as usize, end as usize)
+                }
+                _ => String::new(),
+            }
+        }
+    }
+}
+
+/// Read the inclusive 1-indexed line range `[start, end]` of `code_path`
+/// under `project_root`, or an empty string if the file or range is gone.
+fn read_source_region(project_root: &Path, code_path: &str, start: usize, end: usize) -> String {
+    let Ok(source) = std::fs::read_to_string(project_root.join(code_path)) else {
+        return String::new();
+    };
+    let lines: Vec<&str> = source.lines().collect();
+    if start == 0 || start > end || end > lines.len() {
+        return String::new();
+    }
+    lines[start - 1..end].join("\n")
+}
+
+/// Split `text` into lowercase tokens on non-alphanumeric boundaries and
+/// identifier casing boundaries (snake_case, CamelCase), so e.g.
+/// `parse_HttpRequest` tokenizes the same as "Parse Http Request".
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+
+    for ch in text.chars() {
+        if ch.is_alphanumeric() {
+            if ch.is_uppercase() && prev_lower {
+                push_token(&mut current, &mut tokens);
+            }
+            current.push(ch);
+            prev_lower = ch.is_lowercase();
+        } else {
+            push_token(&mut current, &mut tokens);
+            prev_lower = false;
+        }
+    }
+    push_token(&mut current, &mut tokens);
+
+    tokens
+}
+
+fn push_token(current: &mut String, tokens: &mut Vec<String>) {
+    if !current.is_empty() {
+        tokens.push(std::mem::take(current).to_lowercase());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn exact_match_outranks_a_fuzzy_typo_match() {
+        let entries: HashMap<String, Value> = [
+            (
+                "probe:exact".to_string(),
+                json!({"display-name": "request", "__file_path": "exact.md"}),
+            ),
+            (
+                "probe:fuzzy".to_string(),
+                json!({"display-name": "requist", "__file_path": "fuzzy.md"}),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let index = build_index(Path::new("."), StructureForm::Json, entries);
+        let hits = index.search("request");
+
+        assert_eq!(hits.len(), 2, "expected both the exact and fuzzy entry to match");
+        assert_eq!(hits[0].0.probe_name, "probe:exact");
+        assert_eq!(hits[1].0.probe_name, "probe:fuzzy");
+        assert!(
+            hits[0].1 > hits[1].1,
+            "exact match score {} should exceed fuzzy match score {}",
+            hits[0].1,
+            hits[1].1
+        );
+    }
+}
@@ -2,16 +2,65 @@
 //!
 //! Check specification status and manage spec certs.
 
-use crate::certs::{create_cert, get_existing_certs};
+use crate::certs::{cert_status, create_cert, get_existing_certs, hash_source_region, CertStatus};
 use crate::config::ConfigPaths;
-use crate::probe;
-use crate::utils::{display_menu, run_command};
-use std::collections::HashSet;
+use crate::diagnostics;
+use crate::utils::{check_probe_verus_or_exit, display_menu, run_command, status};
 use anyhow::{bail, Context, Result};
-use serde_json::Value;
-use std::collections::HashMap;
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+/// Typed view of a stubs.json entry. `code-path` is required — `create`
+/// always writes it — while `code-name`/`display-name` may still be `null`
+/// before `atomize` has run, and `specified` defaults to `false` before
+/// `specify` has run. Every other field (`code-line`, `dependencies`,
+/// `code-hash`, `license`, ...) round-trips untouched through `extra`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Stub {
+    #[serde(rename = "code-path")]
+    code_path: String,
+    #[serde(rename = "code-name")]
+    code_name: Option<String>,
+    #[serde(rename = "display-name")]
+    display_name: Option<String>,
+    #[serde(default)]
+    specified: bool,
+    #[serde(rename = "spec-text", skip_serializing_if = "Option::is_none")]
+    spec_text: Option<Value>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl Stub {
+    /// The source line the stub was anchored to. Stubs reaching
+    /// certification logic are expected to carry a numeric `code-line` in
+    /// `extra` — returning a `Result` here (rather than defaulting to `0`
+    /// or an empty hash) means a missing one surfaces as an actionable
+    /// error instead of quietly miscategorizing the stub's cert status.
+    fn code_line(&self) -> Result<u32> {
+        self.extra.get_u64("code-line").map(|l| l as u32)
+    }
+}
+
+/// Extension trait for pulling fields out of a dynamic JSON object with a
+/// contextful error instead of silently falling back to a default — used
+/// for the handful of fields (here, `Stub::extra`) that don't go through
+/// a typed struct field.
+trait ValueExt {
+    fn get_u64(&self, key: &str) -> Result<u64>;
+}
+
+impl ValueExt for Map<String, Value> {
+    fn get_u64(&self, key: &str) -> Result<u64> {
+        self.get(key)
+            .and_then(|v| v.as_u64())
+            .with_context(|| format!("missing or non-numeric field `{}`", key))
+    }
+}
+
 /// Run the specify subcommand.
 ///
 /// Flow:
@@ -22,15 +71,31 @@ use std::path::{Path, PathBuf};
 /// 5. Display menu and create certs for selected functions
 /// 6. Update specified status in stubs based on certification
 /// 7. Write updated stubs back to stubs.json
-pub fn run(project_root: PathBuf) -> Result<()> {
+///
+/// In `check` mode, steps 5-7 are replaced with a read-only report: any
+/// spec-text'd function lacking a fresh cert, or any stub whose on-disk
+/// `specified` flag no longer matches what step 6 would compute, is listed
+/// and the command exits non-zero — stubs.json is never rewritten.
+///
+/// If `all`, `select`, or `from_file` is given, step 5 skips the
+/// interactive menu and certifies the resolved selection instead — see
+/// `resolve_batch_selection`.
+pub fn run(
+    project_root: PathBuf,
+    check: bool,
+    all: bool,
+    select: Vec<String>,
+    from_file: Option<PathBuf>,
+) -> Result<()> {
     let project_root = project_root
         .canonicalize()
         .context("Failed to resolve project root")?;
     let config = ConfigPaths::load(&project_root)?;
 
     // Load stubs from stubs.json
-    let mut stubs_data = read_stubs_json(&config.structure_json_path)?;
-    println!("Loaded {} stubs from stubs.json", stubs_data.len());
+    let on_disk_stubs = read_stubs_json(&config.structure_json_path)?;
+    let mut stubs_data = on_disk_stubs.clone();
+    status!("Loaded {} stubs from stubs.json", stubs_data.len());
 
     // Run probe-verus specify to get spec info
     let specs_path = config.verilib_path.join("specs.json");
@@ -39,13 +104,30 @@ pub fn run(project_root: PathBuf) -> Result<()> {
     // Enrich stubs with spec-text (only for functions where specified=true)
     incorporate_spec_text(&mut stubs_data, &specs_data);
 
-    // Find stubs with spec-text that are not yet certified
+    // Find stubs with spec-text that are not yet certified, or whose cert
+    // has gone stale because the source or tooling changed since certification.
     let existing_certs = get_existing_certs(&config.certs_specify_dir)?;
-    println!("Found {} existing certs", existing_certs.len());
-    let uncertified = find_uncertified_functions(&stubs_data, &existing_certs);
+    status!("Found {} existing certs", existing_certs.len());
+    let tool_version = crate::utils::get_tool_version("probe-verus")?;
+    let uncertified = find_uncertified_functions(
+        &stubs_data,
+        &config.certs_specify_dir,
+        &project_root,
+        &tool_version,
+    )?;
+
+    if check {
+        return report_specify_drift(&uncertified, &on_disk_stubs, &mut stubs_data, &existing_certs);
+    }
 
-    // Display menu and create certs for selected functions
-    let newly_certified = collect_certifications(&uncertified, &config.certs_specify_dir)?;
+    // Display menu and create certs for selected functions, unless a batch
+    // selector was given, in which case certify that selection non-interactively.
+    let newly_certified = if all || !select.is_empty() || from_file.is_some() {
+        let selected = resolve_batch_selection(&uncertified, all, &select, from_file.as_deref())?;
+        collect_certifications_batch(&uncertified, &config.certs_specify_dir, &project_root, &tool_version, &selected)?
+    } else {
+        collect_certifications(&uncertified, &config.certs_specify_dir, &project_root, &tool_version)?
+    };
 
     // Update specified status based on all certified functions
     let all_certified: HashSet<String> = existing_certs
@@ -57,71 +139,183 @@ pub fn run(project_root: PathBuf) -> Result<()> {
     // Write updated stubs back to stubs.json
     write_stubs_json(&config.structure_json_path, &stubs_data)?;
 
-    println!("Done.");
+    // Write a durable coverage report so progress can be diffed over time
+    // instead of re-derived from this run's status! counters.
+    let still_uncertified: HashMap<String, Stub> = uncertified
+        .into_iter()
+        .filter(|(_, stub)| {
+            stub.code_name
+                .as_deref()
+                .map(|name| !all_certified.contains(name))
+                .unwrap_or(true)
+        })
+        .collect();
+    let report = build_coverage_report(&stubs_data, &specs_data, &still_uncertified, &all_certified);
+    write_coverage_report(&config.verilib_path, &report)?;
+
+    status!("Done.");
     Ok(())
 }
 
-/// Find stubs with spec-text that are not yet certified.
-fn find_uncertified_functions(
-    stubs_data: &HashMap<String, Value>,
+/// Report, without writing anything, which functions with spec-text lack a
+/// fresh cert and which stubs' `specified` flag would change if `specify`
+/// were run for real. Returns an error if anything is out of date, so CI
+/// can fail the build the same way `fmt --check` does.
+fn report_specify_drift(
+    uncertified: &HashMap<String, Stub>,
+    on_disk_stubs: &HashMap<String, Stub>,
+    stubs_data: &mut HashMap<String, Stub>,
     existing_certs: &HashSet<String>,
-) -> HashMap<String, Value> {
-    // Find stubs which have "spec-text" field
-    let stubs_with_specs: HashMap<String, Value> = stubs_data
+) -> Result<()> {
+    let mut problems = Vec::new();
+
+    let mut uncertified_names: Vec<String> = uncertified
+        .values()
+        .filter_map(|stub| stub.code_name.clone())
+        .collect();
+    uncertified_names.sort();
+    for name in uncertified_names {
+        problems.push(format!(
+            "UNCERTIFIED  '{}' has spec-text but no fresh cert",
+            name
+        ));
+    }
+
+    // Compute what `specified` flags update_stubs_specification_status would
+    // write based on certs that already exist (check mode never creates new
+    // ones), and diff that against what's actually on disk.
+    update_stubs_specification_status(stubs_data, existing_certs);
+    let mut stale_paths: Vec<(String, bool)> = stubs_data
         .iter()
-        .filter(|(_, stub)| stub.get("spec-text").is_some())
+        .filter_map(|(path, entry)| {
+            let expected = entry.specified;
+            let actual = on_disk_stubs.get(path).map(|e| e.specified).unwrap_or(false);
+            (expected != actual).then_some((path.clone(), expected))
+        })
+        .collect();
+    stale_paths.sort_by(|a, b| a.0.cmp(&b.0));
+    for (path, expected) in stale_paths {
+        problems.push(format!(
+            "STALE        {}: stubs.json 'specified' should be {}",
+            path, expected
+        ));
+    }
+
+    if problems.is_empty() {
+        status!("specify --check: stubs.json is up to date and every specified function is certified.");
+        return Ok(());
+    }
+
+    status!("specify --check found {} issue(s):", problems.len());
+    for problem in &problems {
+        status!("  {}", problem);
+    }
+
+    bail!("{} specify issue(s) found; run `specify` to fix", problems.len());
+}
+
+/// Find stubs with spec-text that are not yet certified, or whose existing
+/// cert is stale (source or tool version drifted since it was issued).
+fn find_uncertified_functions(
+    stubs_data: &HashMap<String, Stub>,
+    certs_dir: &Path,
+    project_root: &Path,
+    tool_version: &str,
+) -> Result<HashMap<String, Stub>> {
+    // Find stubs which have spec-text
+    let stubs_with_specs: HashMap<String, Stub> = stubs_data
+        .iter()
+        .filter(|(_, stub)| stub.spec_text.is_some())
         .map(|(k, v)| (k.clone(), v.clone()))
         .collect();
-    println!(
+    status!(
         "\nFound {} stubs with spec-text",
         stubs_with_specs.len()
     );
 
-    // Filter out existing certs (by code-name)
-    let uncertified: HashMap<String, Value> = stubs_with_specs
-        .into_iter()
-        .filter(|(_, stub)| {
-            let code_name = stub
-                .get("code-name")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            !existing_certs.contains(code_name)
-        })
-        .collect();
+    let mut uncertified = HashMap::new();
+    let mut stale_count = 0;
 
-    println!(
-        "Found {} stubs needing certification",
-        uncertified.len()
+    for (stub_path, stub) in stubs_with_specs {
+        let code_name = stub.code_name.as_deref().with_context(|| {
+            format!("stub `{}` has spec-text but is missing required field `code-name`", stub_path)
+        })?;
+        let code_line = stub.code_line().with_context(|| {
+            format!("stub `{}` has spec-text but is missing required field `code-line`", stub_path)
+        })?;
+
+        let source_hash = hash_source_region(project_root, &stub.code_path, Some(code_line)).unwrap_or_default();
+
+        match cert_status(certs_dir, code_name, &source_hash, tool_version)? {
+            CertStatus::Fresh => {}
+            CertStatus::Stale => {
+                stale_count += 1;
+                uncertified.insert(stub_path, stub);
+            }
+            CertStatus::Missing => {
+                uncertified.insert(stub_path, stub);
+            }
+        }
+    }
+
+    status!(
+        "Found {} stubs needing certification ({} stale)",
+        uncertified.len(),
+        stale_count
     );
 
-    uncertified
+    Ok(uncertified)
 }
 
 /// Display menu for uncertified functions and create certs for selected ones.
 /// Returns the set of newly certified code-names.
 fn collect_certifications(
-    uncertified: &HashMap<String, Value>,
+    uncertified: &HashMap<String, Stub>,
     certs_dir: &Path,
+    project_root: &Path,
+    tool_version: &str,
 ) -> Result<HashSet<String>> {
     let mut newly_certified = HashSet::new();
 
     if uncertified.is_empty() {
-        println!("\nAll functions with specs in structure are already validated!");
+        status!("\nAll functions with specs in structure are already validated!");
         return Ok(newly_certified);
     }
 
-    println!(
+    status!(
         "\n{} functions with specs need certification",
         uncertified.len()
     );
 
-    let mut uncertified_list: Vec<(String, Value)> = uncertified
+    let mut uncertified_list: Vec<(String, Stub)> = uncertified
         .iter()
         .map(|(k, v)| (k.clone(), v.clone()))
         .collect();
     uncertified_list.sort_by(|a, b| a.0.cmp(&b.0));
 
-    let selected_indices = display_menu(&uncertified_list, |i, _stub_path, stub| {
+    for (_stub_path, stub) in &uncertified_list {
+        if let Ok(line) = stub.code_line() {
+            status!(
+                "{}",
+                diagnostics::render_or_fallback(
+                    project_root,
+                    &stub.code_path,
+                    line,
+                    diagnostics::Severity::Warning,
+                    "no spec certificate",
+                )
+            );
+        }
+    }
+
+    // display_menu renders generic JSON, so hand it a view of each stub
+    // rather than threading a second rendering path through the typed model.
+    let menu_items: Vec<(String, Value)> = uncertified_list
+        .iter()
+        .map(|(path, stub)| (path.clone(), serde_json::to_value(stub).unwrap_or(Value::Null)))
+        .collect();
+
+    let selected_indices = display_menu(&menu_items, crate::StructureType::DalekLite, |i, _stub_path, stub| {
         let display_name = stub
             .get("display-name")
             .and_then(|v| v.as_str())
@@ -141,30 +335,21 @@ fn collect_certifications(
     })?;
 
     if selected_indices.is_empty() {
-        println!("\nNo functions selected.");
+        status!("\nNo functions selected.");
         return Ok(newly_certified);
     }
 
-    println!(
+    status!(
         "\nCreating certs for {} functions...",
         selected_indices.len()
     );
 
     for idx in &selected_indices {
-        let (_stub_path, stub) = &uncertified_list[*idx];
-        let code_name = stub
-            .get("code-name")
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
-        newly_certified.insert(code_name.to_string());
-        let cert_path = create_cert(certs_dir, code_name)?;
-        println!(
-            "  Created: {}",
-            cert_path.file_name().unwrap_or_default().to_string_lossy()
-        );
+        let (stub_path, stub) = &uncertified_list[*idx];
+        newly_certified.insert(certify_stub(stub_path, stub, certs_dir, project_root, tool_version)?);
     }
 
-    println!(
+    status!(
         "\nCreated {} cert files in {}",
         selected_indices.len(),
         certs_dir.display()
@@ -173,24 +358,135 @@ fn collect_certifications(
     Ok(newly_certified)
 }
 
+/// Create a cert for one stub and return its code-name. Shared by the
+/// interactive menu and the non-interactive batch selection path.
+fn certify_stub(
+    stub_path: &str,
+    stub: &Stub,
+    certs_dir: &Path,
+    project_root: &Path,
+    tool_version: &str,
+) -> Result<String> {
+    let code_name = stub
+        .code_name
+        .as_deref()
+        .with_context(|| format!("stub `{}` is missing required field `code-name`", stub_path))?;
+
+    let code_line = stub
+        .code_line()
+        .with_context(|| format!("stub `{}` is missing required field `code-line`", stub_path))?;
+    let source_hash = hash_source_region(project_root, &stub.code_path, Some(code_line))?;
+
+    let cert_path = create_cert(certs_dir, code_name, &source_hash, tool_version)?;
+    status!(
+        "  Created: {}",
+        cert_path.file_name().unwrap_or_default().to_string_lossy()
+    );
+
+    Ok(code_name.to_string())
+}
+
+/// Resolve `--all`/`--select`/`--from-file` against `uncertified` into the
+/// set of stub paths to certify. `--select` globs match either
+/// `code-name` or `code-path`; `--from-file` lines are exact `code-name`s.
+/// Any selector that matches nothing is printed as a warning so a typo'd
+/// glob or stale members file doesn't silently certify zero functions.
+fn resolve_batch_selection(
+    uncertified: &HashMap<String, Stub>,
+    all: bool,
+    select: &[String],
+    from_file: Option<&Path>,
+) -> Result<BTreeSet<String>> {
+    let mut selected = BTreeSet::new();
+
+    if all {
+        selected.extend(uncertified.keys().cloned());
+    }
+
+    for pattern_str in select {
+        let pattern = Pattern::new(pattern_str).with_context(|| format!("invalid --select glob `{}`", pattern_str))?;
+        let mut matched = false;
+        for (stub_path, stub) in uncertified {
+            let code_name = stub.code_name.as_deref().unwrap_or("");
+            if pattern.matches(code_name) || pattern.matches(&stub.code_path) {
+                selected.insert(stub_path.clone());
+                matched = true;
+            }
+        }
+        if !matched {
+            status!("Warning: --select `{}` matched no uncertified function", pattern_str);
+        }
+    }
+
+    if let Some(path) = from_file {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read --from-file {}", path.display()))?;
+
+        for name in content.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            let mut matched = false;
+            for (stub_path, stub) in uncertified {
+                if stub.code_name.as_deref() == Some(name) {
+                    selected.insert(stub_path.clone());
+                    matched = true;
+                }
+            }
+            if !matched {
+                status!("Warning: --from-file entry `{}` matched no uncertified function", name);
+            }
+        }
+    }
+
+    Ok(selected)
+}
+
+/// Non-interactive counterpart to `collect_certifications`: certify exactly
+/// the stub paths in `selected`, skipping the menu entirely.
+fn collect_certifications_batch(
+    uncertified: &HashMap<String, Stub>,
+    certs_dir: &Path,
+    project_root: &Path,
+    tool_version: &str,
+    selected: &BTreeSet<String>,
+) -> Result<HashSet<String>> {
+    let mut newly_certified = HashSet::new();
+
+    if selected.is_empty() {
+        status!("\nNo functions matched the given selectors.");
+        return Ok(newly_certified);
+    }
+
+    status!("\nCreating certs for {} selected functions...", selected.len());
+
+    for stub_path in selected {
+        let stub = uncertified
+            .get(stub_path)
+            .with_context(|| format!("selected stub `{}` is no longer in the uncertified set", stub_path))?;
+        newly_certified.insert(certify_stub(stub_path, stub, certs_dir, project_root, tool_version)?);
+    }
+
+    status!("\nCreated {} cert files in {}", newly_certified.len(), certs_dir.display());
+
+    Ok(newly_certified)
+}
+
 /// Run probe-verus specify and return the results.
 fn run_probe_specify(
     project_root: &Path,
     specs_path: &Path,
     atoms_path: &Path,
 ) -> Result<HashMap<String, Value>> {
-    probe::require_installed()?;
+    check_probe_verus_or_exit()?;
 
     if let Some(parent) = specs_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
-    println!(
+    status!(
         "Running probe-verus specify on {}...",
         project_root.display()
     );
 
-    let output = run_command(
+    run_command(
         "probe-verus",
         &[
             "specify",
@@ -203,16 +499,7 @@ fn run_probe_specify(
         Some(project_root),
     )?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        eprintln!("Error: probe-verus specify failed.");
-        if !stderr.is_empty() {
-            eprintln!("{}", stderr);
-        }
-        bail!("probe-verus specify failed");
-    }
-
-    println!("Specs saved to {}", specs_path.display());
+    status!("Specs saved to {}", specs_path.display());
 
     let content = std::fs::read_to_string(specs_path)?;
     let specs: HashMap<String, Value> = serde_json::from_str(&content)?;
@@ -221,73 +508,176 @@ fn run_probe_specify(
 
 /// Update stubs_data with specification statuses based on certified names.
 fn update_stubs_specification_status(
-    stubs_data: &mut HashMap<String, Value>,
+    stubs_data: &mut HashMap<String, Stub>,
     certified_names: &HashSet<String>,
 ) {
-    for entry in stubs_data.values_mut() {
-        if let Some(obj) = entry.as_object_mut() {
-            let specified = obj
-                .get("code-name")
-                .and_then(|v| v.as_str())
-                .map(|name| certified_names.contains(name))
-                .unwrap_or(false);
-
-            obj.insert("specified".to_string(), Value::Bool(specified));
-        }
+    for stub in stubs_data.values_mut() {
+        stub.specified = stub
+            .code_name
+            .as_deref()
+            .map(|name| certified_names.contains(name))
+            .unwrap_or(false);
     }
 
-    println!("Updated specification status for {} stubs", stubs_data.len());
+    status!("Updated specification status for {} stubs", stubs_data.len());
 }
 
-/// Read stubs.json into a HashMap.
-fn read_stubs_json(stubs_path: &Path) -> Result<HashMap<String, Value>> {
+/// Read stubs.json into a HashMap, deserializing every entry into a typed
+/// `Stub` so a missing or malformed required field fails loudly — naming
+/// both the offending stub and field — instead of silently miscategorizing
+/// it downstream.
+fn read_stubs_json(stubs_path: &Path) -> Result<HashMap<String, Stub>> {
     if !stubs_path.exists() {
         return Ok(HashMap::new());
     }
 
     let content = std::fs::read_to_string(stubs_path)?;
-    let stubs: HashMap<String, Value> = serde_json::from_str(&content)?;
-    Ok(stubs)
+    let raw: HashMap<String, Value> = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not valid JSON", stubs_path.display()))?;
+
+    raw.into_iter()
+        .map(|(stub_path, value)| {
+            let stub: Stub =
+                serde_json::from_value(value).with_context(|| format!("stub `{}` is malformed", stub_path))?;
+            Ok((stub_path, stub))
+        })
+        .collect()
 }
 
 /// Write stubs_data to stubs.json.
-fn write_stubs_json(stubs_path: &Path, stubs_data: &HashMap<String, Value>) -> Result<()> {
+fn write_stubs_json(stubs_path: &Path, stubs_data: &HashMap<String, Stub>) -> Result<()> {
     let content = serde_json::to_string_pretty(stubs_data)?;
     std::fs::write(stubs_path, content)?;
-    println!("Wrote stubs to {}", stubs_path.display());
+    status!("Wrote stubs to {}", stubs_path.display());
     Ok(())
 }
 
 /// Incorporate spec-text from specs_data into stubs_data.
 /// For each stub with a code-name, look up code-name in specs_data
 /// and add "spec-text" field if specified is true.
-fn incorporate_spec_text(
-    stubs_data: &mut HashMap<String, Value>,
-    specs_data: &HashMap<String, Value>,
-) {
+fn incorporate_spec_text(stubs_data: &mut HashMap<String, Stub>, specs_data: &HashMap<String, Value>) {
     let mut count = 0;
     for stub in stubs_data.values_mut() {
-        if let Some(obj) = stub.as_object_mut() {
-            let code_name = obj
-                .get("code-name")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-
-            if let Some(spec_info) = specs_data.get(code_name) {
-                // Only add spec-text if specified is true
-                let is_specified = spec_info
-                    .get("specified")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-
-                if is_specified {
-                    if let Some(spec_text) = spec_info.get("spec-text") {
-                        obj.insert("spec-text".to_string(), spec_text.clone());
-                        count += 1;
-                    }
-                }
+        let Some(code_name) = stub.code_name.as_deref() else {
+            continue;
+        };
+        let Some(spec_info) = specs_data.get(code_name) else {
+            continue;
+        };
+
+        // Only add spec-text if specified is true
+        let is_specified = spec_info.get("specified").and_then(|v| v.as_bool()).unwrap_or(false);
+        if is_specified {
+            if let Some(spec_text) = spec_info.get("spec-text") {
+                stub.spec_text = Some(spec_text.clone());
+                count += 1;
             }
         }
     }
-    println!("Incorporated spec-text for {} stubs", count);
+    status!("Incorporated spec-text for {} stubs", count);
+}
+
+/// Specification-coverage totals for one `specify` run, written to
+/// `.verilib/specify-report.{json,md}` so coverage can be diffed over time
+/// or published, rather than read off this run's `status!` counters.
+#[derive(Debug, Serialize)]
+struct CoverageReport {
+    stub_count: usize,
+    specified_by_probe_count: usize,
+    spec_text_count: usize,
+    certified_count: usize,
+    uncertified: Vec<UncertifiedEntry>,
+}
+
+#[derive(Debug, Serialize)]
+struct UncertifiedEntry {
+    #[serde(rename = "code-name")]
+    code_name: String,
+    #[serde(rename = "code-path")]
+    code_path: String,
+    #[serde(rename = "lines-start", skip_serializing_if = "Option::is_none")]
+    lines_start: Option<u64>,
+}
+
+/// Build the coverage report from the data `run` already has in hand:
+/// `stubs_data` and `specs_data` for the totals, `uncertified` for the
+/// explicit backlog, and `all_certified` to know which stubs ended up
+/// certified by this run.
+fn build_coverage_report(
+    stubs_data: &HashMap<String, Stub>,
+    specs_data: &HashMap<String, Value>,
+    uncertified: &HashMap<String, Stub>,
+    all_certified: &HashSet<String>,
+) -> CoverageReport {
+    let specified_by_probe_count = specs_data
+        .values()
+        .filter(|spec_info| spec_info.get("specified").and_then(|v| v.as_bool()).unwrap_or(false))
+        .count();
+    let spec_text_count = stubs_data.values().filter(|stub| stub.spec_text.is_some()).count();
+    let certified_count = stubs_data
+        .values()
+        .filter(|stub| {
+            stub.code_name
+                .as_deref()
+                .map(|name| all_certified.contains(name))
+                .unwrap_or(false)
+        })
+        .count();
+
+    let mut uncertified_entries: Vec<UncertifiedEntry> = uncertified
+        .values()
+        .map(|stub| UncertifiedEntry {
+            code_name: stub.code_name.clone().unwrap_or_default(),
+            code_path: stub.code_path.clone(),
+            lines_start: stub
+                .spec_text
+                .as_ref()
+                .and_then(|v| v.get("lines-start"))
+                .and_then(|v| v.as_u64()),
+        })
+        .collect();
+    uncertified_entries.sort_by(|a, b| a.code_name.cmp(&b.code_name));
+
+    CoverageReport {
+        stub_count: stubs_data.len(),
+        specified_by_probe_count,
+        spec_text_count,
+        certified_count,
+        uncertified: uncertified_entries,
+    }
+}
+
+/// Write the coverage report as both a machine-readable `specify-report.json`
+/// and a `specify-report.md` summary, alongside the other `.verilib` artifacts.
+fn write_coverage_report(verilib_path: &Path, report: &CoverageReport) -> Result<()> {
+    let json_path = verilib_path.join("specify-report.json");
+    std::fs::write(&json_path, serde_json::to_string_pretty(report)?)?;
+    std::fs::write(verilib_path.join("specify-report.md"), render_coverage_markdown(report))?;
+
+    status!("Wrote specification coverage report to {}", json_path.display());
+    Ok(())
+}
+
+/// Render a human-readable summary of a `CoverageReport`.
+fn render_coverage_markdown(report: &CoverageReport) -> String {
+    let mut out = String::new();
+    out.push_str("# Specification coverage\n\n");
+    out.push_str(&format!("- Stubs: {}\n", report.stub_count));
+    out.push_str(&format!("- Specified by probe-verus: {}\n", report.specified_by_probe_count));
+    out.push_str(&format!("- With spec-text: {}\n", report.spec_text_count));
+    out.push_str(&format!("- Certified: {}\n", report.certified_count));
+    out.push_str(&format!("- Uncertified: {}\n", report.uncertified.len()));
+
+    if !report.uncertified.is_empty() {
+        out.push_str("\n## Uncertified functions\n\n");
+        for entry in &report.uncertified {
+            let location = match entry.lines_start {
+                Some(line) => format!("{}:{}", entry.code_path, line),
+                None => entry.code_path.clone(),
+            };
+            out.push_str(&format!("- `{}` ({})\n", entry.code_name, location));
+        }
+    }
+
+    out
 }
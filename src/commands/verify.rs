@@ -2,24 +2,162 @@
 //!
 //! Run verification and manage verification certs.
 
+use crate::certs::{accepted_certs, hash_source_region, load_cert, sign_cert};
 use crate::config::constants::{BLUEPRINT_VERIFIED_STATUSES, SCIP_PREFIX};
 use crate::config::ConfigPaths;
+use crate::dependency::{propagate_verification, VerificationStatus};
+use crate::diagnostics;
+use crate::trust::{load_signing_key, TrustConfig};
 use crate::utils::{
-    check_scip_atoms_or_exit, create_cert, delete_cert, get_display_name, get_existing_certs,
-    get_structure_names, run_command,
+    check_scip_atoms_or_exit, delete_cert, get_display_name, get_existing_certs,
+    get_structure_code_locations, get_structure_dependencies, get_structure_names,
+    get_tool_version, run_command, status,
 };
 use crate::StructureType;
 use anyhow::{bail, Context, Result};
+use notify::{Event, RecursiveMode, Watcher};
 use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How long to keep absorbing change events into the current batch before
+/// acting, so a burst of editor saves triggers one re-verify, not several.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(400);
 
 /// Run the verify subcommand.
-pub fn run(project_root: PathBuf, verify_only_module: Option<String>) -> Result<()> {
+pub fn run(
+    project_root: PathBuf,
+    verify_only_module: Option<String>,
+    watch: bool,
+    jobs: usize,
+) -> Result<()> {
     let project_root = project_root
         .canonicalize()
         .context("Failed to resolve project root")?;
-    let config = ConfigPaths::load(&project_root)?;
+
+    if watch {
+        return run_watch(&project_root, verify_only_module.as_deref(), jobs);
+    }
+
+    run_once(&project_root, verify_only_module.as_deref(), false, jobs)
+}
+
+/// Watch the project's source tree plus its atoms/blueprint inputs and
+/// re-run verification whenever something relevant changes, restricting
+/// each re-run to the module(s) the change touched when that can be
+/// determined.
+fn run_watch(project_root: &Path, verify_only_module: Option<&str>, jobs: usize) -> Result<()> {
+    status!("Watching {} for changes (Ctrl-C to stop)...", project_root.display());
+    run_once(project_root, verify_only_module, false, jobs)?;
+
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher
+        .watch(project_root, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch {}", project_root.display()))?;
+
+    loop {
+        let Ok(first) = rx.recv() else {
+            return Ok(());
+        };
+        let mut changed = event_paths(first);
+
+        // Keep absorbing events until the stream goes quiet for a beat.
+        loop {
+            match rx.recv_timeout(WATCH_DEBOUNCE) {
+                Ok(event) => changed.extend(event_paths(event)),
+                Err(mpsc::RecvTimeoutError::Timeout) => break,
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+
+        changed.retain(|path| !is_ignored_path(project_root, path));
+        if changed.is_empty() {
+            continue;
+        }
+
+        let module = verify_only_module
+            .map(str::to_string)
+            .or_else(|| affected_module(project_root, &changed));
+
+        match &module {
+            Some(m) => status!("\n--- change detected, re-verifying module '{}' ---", m),
+            None => status!("\n--- change detected, re-verifying ---"),
+        }
+
+        if let Err(err) = run_once(project_root, module.as_deref(), true, jobs) {
+            eprintln!("Error: {err:#}");
+        }
+    }
+}
+
+/// Flatten a `notify` event into its changed paths, discarding errors.
+fn event_paths(event: notify::Result<Event>) -> Vec<PathBuf> {
+    event.map(|e| e.paths).unwrap_or_default()
+}
+
+/// Whether a changed path is our own bookkeeping (certs, config, signing
+/// key) rather than project source or structure input, so watch mode
+/// doesn't re-trigger itself on the cert writes from its own previous run.
+fn is_ignored_path(project_root: &Path, path: &Path) -> bool {
+    let Ok(rel) = path.strip_prefix(project_root) else {
+        return false;
+    };
+    rel.starts_with(".git")
+        || rel.starts_with("target")
+        || rel.starts_with(Path::new(".verilib").join("certs"))
+        || rel == Path::new(".verilib/config.toml")
+        || rel == Path::new(".verilib/signer.key")
+}
+
+/// If every changed path maps to exactly one `code-module` in the atoms
+/// data, return it so the re-run can be scoped with `--verify-only-module`.
+/// Returns `None` (meaning: re-verify everything) when the project isn't
+/// dalek-lite, the atoms file can't be read, or the change spans zero or
+/// more than one module — restricting scope is only safe when it's
+/// unambiguous.
+fn affected_module(project_root: &Path, changed: &[PathBuf]) -> Option<String> {
+    let config = ConfigPaths::load(project_root).ok()?;
+    if config.config.get_structure_type().ok()? != StructureType::DalekLite {
+        return None;
+    }
+
+    let content = std::fs::read_to_string(&config.atoms_path).ok()?;
+    let atoms: HashMap<String, Value> = serde_json::from_str(&content).ok()?;
+
+    let mut modules = HashSet::new();
+    for atom in atoms.values() {
+        let code_path = atom.get("code-path").and_then(|v| v.as_str())?;
+        let code_module = atom.get("code-module").and_then(|v| v.as_str())?;
+        let abs_path = project_root.join(code_path);
+        if changed.iter().any(|p| p == &abs_path) {
+            modules.insert(code_module.to_string());
+        }
+    }
+
+    if modules.len() == 1 {
+        modules.into_iter().next()
+    } else {
+        None
+    }
+}
+
+/// Run verification once: compute verified/failed sets, sign or revoke
+/// verification certs accordingly, and print the results. When `quiet` is
+/// set (watch-mode re-runs), the top-level verification summary is skipped
+/// and only the cert change report is printed. Its signed list names every
+/// fully verified function re-signed this run, not only those whose status
+/// changed since the last run.
+fn run_once(
+    project_root: &Path,
+    verify_only_module: Option<&str>,
+    quiet: bool,
+    jobs: usize,
+) -> Result<()> {
+    let config = ConfigPaths::load(project_root)?;
 
     let structure_type = config.config.get_structure_type()?;
     let structure_form = config.config.get_structure_form()?;
@@ -29,24 +167,33 @@ pub fn run(project_root: PathBuf, verify_only_module: Option<String>) -> Result<
             if verify_only_module.is_some() {
                 eprintln!("Warning: --verify-only-module is ignored for blueprint type");
             }
+            if jobs > 1 {
+                eprintln!("Warning: --jobs is ignored for blueprint type");
+            }
             get_blueprint_verification_results(&config.blueprint_json_path)?
         }
 
         StructureType::DalekLite => {
-            let verification_path = config.verilib_path.join("verification.json");
-            let verification_data = run_scip_verify(
-                &project_root,
-                &verification_path,
-                &config.atoms_path,
-                verify_only_module.as_deref(),
-            )?;
+            let verification_data = if jobs > 1 && verify_only_module.is_none() {
+                run_scip_verify_parallel(project_root, &config, jobs)?
+            } else {
+                let verification_path = config.verilib_path.join("verification.json");
+                run_scip_verify(
+                    project_root,
+                    &verification_path,
+                    &config.atoms_path,
+                    verify_only_module,
+                )?
+            };
             get_verification_results(&verification_data)
         }
     };
 
-    println!("\nVerification summary:");
-    println!("  Verified: {}", verified_funcs.len());
-    println!("  Failed: {}", failed_funcs.len());
+    if !quiet {
+        status!("\nVerification summary:");
+        status!("  Verified: {}", verified_funcs.len());
+        status!("  Failed: {}", failed_funcs.len());
+    }
 
     let structure_names = get_structure_names(
         structure_type,
@@ -54,7 +201,6 @@ pub fn run(project_root: PathBuf, verify_only_module: Option<String>) -> Result<
         &config.structure_root,
         &config.structure_json_path,
     )?;
-    println!("  Functions in structure: {}", structure_names.len());
 
     let verified_in_structure: HashSet<_> = verified_funcs
         .intersection(&structure_names)
@@ -64,82 +210,369 @@ pub fn run(project_root: PathBuf, verify_only_module: Option<String>) -> Result<
         .intersection(&structure_names)
         .cloned()
         .collect();
-    println!("  Verified in structure: {}", verified_in_structure.len());
-    println!("  Failed in structure: {}", failed_in_structure.len());
 
-    let existing_certs = get_existing_certs(&config.certs_verify_dir)?;
-    println!("  Existing certs: {}", existing_certs.len());
+    // A function whose own proof passes but which calls an unverified (or
+    // transitively unverified) helper isn't actually trustworthy, so certs
+    // are only issued once verification has been propagated over the call
+    // graph — see `crate::dependency`.
+    let dependencies = get_structure_dependencies(
+        structure_type,
+        structure_form,
+        &config.structure_root,
+        &config.structure_json_path,
+    )?;
+    let transitive_status = propagate_verification(&verified_funcs, &failed_funcs, &dependencies);
 
-    let to_create: HashSet<_> = verified_in_structure
-        .difference(&existing_certs)
+    let fully_verified: HashSet<String> = structure_names
+        .iter()
+        .filter(|name| transitive_status.get(*name).is_some_and(VerificationStatus::is_verified))
         .cloned()
         .collect();
-    let to_delete: HashSet<_> = failed_in_structure
-        .intersection(&existing_certs)
-        .cloned()
+    let blocked: Vec<(String, String)> = structure_names
+        .iter()
+        .filter_map(|name| match transitive_status.get(name) {
+            Some(VerificationStatus::Blocked { blocking }) => Some((name.clone(), blocking.clone())),
+            _ => None,
+        })
         .collect();
 
-    let mut created = Vec::new();
-    let mut deleted = Vec::new();
+    let existing_certs = get_existing_certs(&config.certs_verify_dir)?;
 
-    let mut to_create_sorted: Vec<_> = to_create.into_iter().collect();
-    to_create_sorted.sort();
-    for name in to_create_sorted {
-        let cert_path = create_cert(&config.certs_verify_dir, &name)?;
-        created.push((name, cert_path));
+    if !quiet {
+        status!("  Functions in structure: {}", structure_names.len());
+        status!("  Verified in structure: {}", verified_in_structure.len());
+        status!("  Failed in structure: {}", failed_in_structure.len());
+        status!("  Fully verified (transitive): {}", fully_verified.len());
+        status!("  Locally verified but blocked: {}", blocked.len());
+        status!("  Cert files on disk: {}", existing_certs.len());
     }
 
-    let mut to_delete_sorted: Vec<_> = to_delete.into_iter().collect();
-    to_delete_sorted.sort();
-    for name in to_delete_sorted {
+    let trust_config = TrustConfig::load(project_root)
+        .context("verify requires a web-of-trust policy at .verilib/trust.toml")?;
+    let signing_key = load_signing_key(project_root)?;
+    let verifier_key_id = crate::trust::key_id(&signing_key);
+    let my_weight = trust_config.weight_of(&verifier_key_id);
+    if my_weight == 0 {
+        bail!(
+            "This machine's verifier key ({}) is not in the trust policy at .verilib/trust.toml",
+            verifier_key_id
+        );
+    }
+
+    let code_locations = get_structure_code_locations(
+        structure_type,
+        structure_form,
+        &config.structure_root,
+        &config.structure_json_path,
+    )?;
+    let tool_version = match structure_type {
+        StructureType::Blueprint => get_tool_version("leanblueprint")?,
+        StructureType::DalekLite => get_tool_version("scip-atoms")?,
+    };
+
+    let current_hashes: HashMap<String, (String, String)> = structure_names
+        .iter()
+        .filter_map(|name| {
+            let (code_path, line) = code_locations.get(name)?;
+            let hash = hash_source_region(project_root, code_path, Some(*line)).unwrap_or_default();
+            Some((name.clone(), (hash, tool_version.clone())))
+        })
+        .collect();
+
+    // Sign (or refresh this verifier's signature on) every function that is
+    // fully verified: it passes locally AND every transitive dependency does too.
+    let mut signed = Vec::new();
+    let mut fully_verified_sorted: Vec<_> = fully_verified.iter().cloned().collect();
+    fully_verified_sorted.sort();
+    for name in &fully_verified_sorted {
+        let Some((source_hash, _)) = current_hashes.get(name) else {
+            continue;
+        };
+        let cert_path = sign_cert(&config.certs_verify_dir, name, source_hash, &tool_version, &signing_key)?;
+        signed.push((name.clone(), cert_path));
+    }
+
+    // Revoke certs for anything that failed verification this run, or that
+    // verifies locally but is blocked on an unverified dependency.
+    let mut deleted = Vec::new();
+    let mut failed_sorted: Vec<_> = failed_in_structure.iter().cloned().collect();
+    failed_sorted.sort();
+    for name in failed_sorted {
         if let Some(cert_path) = delete_cert(&config.certs_verify_dir, &name)? {
             deleted.push((name, cert_path));
         }
     }
 
-    println!();
-    println!("{}", "=".repeat(60));
-    println!("VERIFICATION CERT CHANGES");
-    println!("{}", "=".repeat(60));
+    let mut blocked_revoked = Vec::new();
+    let mut blocked_sorted = blocked.clone();
+    blocked_sorted.sort();
+    for (name, blocking) in blocked_sorted {
+        if let Some(cert_path) = delete_cert(&config.certs_verify_dir, &name)? {
+            blocked_revoked.push((name, blocking, cert_path));
+        }
+    }
+
+    // Invalidate certs whose recorded source hash or tool version no longer
+    // matches the current one, even for names this run didn't re-verify (e.g. a
+    // `--verify-only-module` run scoped elsewhere). Without this, a
+    // function that verified once keeps reporting as verified forever
+    // after its body is edited.
+    let mut stale_invalidated = Vec::new();
+    let mut existing_sorted: Vec<_> = existing_certs.iter().cloned().collect();
+    existing_sorted.sort();
+    for name in existing_sorted {
+        if signed.iter().any(|(n, _)| *n == name)
+            || deleted.iter().any(|(n, _)| *n == name)
+            || blocked_revoked.iter().any(|(n, _, _)| *n == name)
+        {
+            continue;
+        }
+        let Some((current_hash, current_tool_version)) = current_hashes.get(&name) else {
+            continue;
+        };
+        let Some(cert) = load_cert(&config.certs_verify_dir, &name)? else {
+            continue;
+        };
+        if cert.source_hash != *current_hash || cert.tool_version != *current_tool_version {
+            if let Some(cert_path) = delete_cert(&config.certs_verify_dir, &name)? {
+                stale_invalidated.push((name, cert_path));
+            }
+        }
+    }
+
+    // A function counts as verified only once its cert accumulates enough
+    // trust weight, not merely because a cert file exists.
+    let accepted = accepted_certs(&config.certs_verify_dir, &current_hashes, &trust_config)?;
+    let unaccepted_with_cert: Vec<_> = fully_verified_sorted
+        .iter()
+        .filter(|name| !accepted.contains(*name))
+        .cloned()
+        .collect();
 
-    if !created.is_empty() {
-        println!("\n✓ Created {} new certs:", created.len());
-        for (name, _) in &created {
+    status!();
+    status!("{}", "=".repeat(60));
+    status!("VERIFICATION CERT CHANGES");
+    status!("{}", "=".repeat(60));
+
+    if !signed.is_empty() {
+        status!("\n✓ Signed {} cert(s) with this verifier's key:", signed.len());
+        for (name, _) in &signed {
             let display_name = get_display_name(name);
-            println!("  + {}", display_name);
-            println!("    {}", name);
+            status!("  + {}", display_name);
+            status!("    {}", name);
         }
     } else {
-        println!("\n✓ No new certs created");
+        status!("\n✓ No certs signed");
     }
 
     if !deleted.is_empty() {
-        println!("\n✗ Deleted {} certs (verification failed):", deleted.len());
+        status!("\n✗ Revoked {} cert(s) (verification failed):", deleted.len());
         for (name, _) in &deleted {
             let display_name = get_display_name(name);
-            println!("  - {}", display_name);
-            println!("    {}", name);
+            status!("  - {}", display_name);
+            status!("    {}", name);
+            if let Some((code_path, line)) = code_locations.get(name) {
+                status!(
+                    "{}",
+                    diagnostics::render_or_fallback(
+                        project_root,
+                        code_path,
+                        *line,
+                        diagnostics::Severity::Error,
+                        "no verification cert",
+                    )
+                );
+            }
         }
     } else {
-        println!("\n✓ No certs deleted");
+        status!("\n✓ No certs revoked");
     }
 
-    println!();
-    println!("{}", "=".repeat(60));
-    let final_certs = existing_certs.len() + created.len() - deleted.len();
-    println!(
-        "Total certs: {} → {}",
-        existing_certs.len(),
-        final_certs
-    );
-    println!("  Created: +{}", created.len());
-    println!("  Deleted: -{}", deleted.len());
-    println!("{}", "=".repeat(60));
+    if !stale_invalidated.is_empty() {
+        status!(
+            "\n⟳ Invalidated {} stale cert(s) (source or tool version changed since last sign-off):",
+            stale_invalidated.len()
+        );
+        for (name, _) in &stale_invalidated {
+            let display_name = get_display_name(name);
+            status!("  ~ {}", display_name);
+            status!("    {}", name);
+        }
+    }
+
+    if !blocked_revoked.is_empty() {
+        status!(
+            "\n⊘ {} cert(s) revoked — verifies locally but depends on an unverified function:",
+            blocked_revoked.len()
+        );
+        for (name, blocking, _) in &blocked_revoked {
+            let display_name = get_display_name(name);
+            status!("  - {} (blocked on {})", display_name, blocking);
+            status!("    {}", name);
+        }
+    }
+
+    if !unaccepted_with_cert.is_empty() {
+        status!(
+            "\n⚠ {} function(s) passed verification but don't yet reach trust threshold {} (summed signer weight short):",
+            unaccepted_with_cert.len(),
+            trust_config.threshold
+        );
+        for name in &unaccepted_with_cert {
+            status!("    {}", name);
+        }
+    }
+
+    status!();
+    status!("{}", "=".repeat(60));
+    status!("Accepted (trust threshold reached): {}", accepted.len());
+    status!("  Signed this run: {}", signed.len());
+    status!("  Revoked (failed): {}", deleted.len());
+    status!("  Revoked (blocked on dependency): {}", blocked_revoked.len());
+    status!("  Stale invalidations: {}", stale_invalidated.len());
+    status!("{}", "=".repeat(60));
 
     Ok(())
 }
 
-/// Run scip-atoms verify and return the results.
+/// All distinct `code-module` values recorded in the atoms data, sorted for
+/// deterministic fan-out order. Returns an empty list (rather than erroring)
+/// when the atoms file is missing, so callers can fall back to a single
+/// whole-project run.
+fn list_modules(atoms_path: &Path) -> Result<Vec<String>> {
+    if !atoms_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(atoms_path)?;
+    let atoms: HashMap<String, Value> = serde_json::from_str(&content)?;
+
+    let mut modules: HashSet<String> = HashSet::new();
+    for atom in atoms.values() {
+        if let Some(module) = atom.get("code-module").and_then(|v| v.as_str()) {
+            if !module.is_empty() {
+                modules.insert(module.to_string());
+            }
+        }
+    }
+
+    let mut modules: Vec<String> = modules.into_iter().collect();
+    modules.sort();
+    Ok(modules)
+}
+
+/// Run `scip-atoms verify` once per module across a bounded pool of up to
+/// `jobs` worker threads, merging each module's verified/failed functions
+/// into a single combined result. Falls back to one whole-project
+/// invocation if the atoms data doesn't record any modules. The
+/// scip-atoms invocations themselves run one at a time under
+/// `VERIFY_DATA_LOCK`; `jobs` does not make them overlap.
+fn run_scip_verify_parallel(
+    project_root: &Path,
+    config: &ConfigPaths,
+    jobs: usize,
+) -> Result<HashMap<String, Value>> {
+    let modules = list_modules(&config.atoms_path)?;
+    if modules.is_empty() {
+        let verification_path = config.verilib_path.join("verification.json");
+        return run_scip_verify(project_root, &verification_path, &config.atoms_path, None);
+    }
+
+    status!(
+        "Running scip-atoms verify across {} module(s) with up to {} worker(s)...",
+        modules.len(),
+        jobs
+    );
+
+    let queue: Mutex<VecDeque<String>> = Mutex::new(modules.into_iter().collect());
+    let print_lock: Mutex<()> = Mutex::new(());
+    let results: Mutex<Vec<(String, Result<HashMap<String, Value>>)>> = Mutex::new(Vec::new());
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| loop {
+                let module = {
+                    let mut queue = queue.lock().unwrap();
+                    queue.pop_front()
+                };
+                let Some(module) = module else {
+                    break;
+                };
+
+                let encoded = crate::certs::encode_name(&module);
+                let verification_path = config
+                    .verilib_path
+                    .join(format!("verification-{encoded}.json"));
+
+                let outcome = run_scip_verify(
+                    project_root,
+                    &verification_path,
+                    &config.atoms_path,
+                    Some(module.as_str()),
+                );
+
+                {
+                    let _guard = print_lock.lock().unwrap();
+                    match &outcome {
+                        Ok(data) => {
+                            let (verified, failed) = get_verification_results(data);
+                            status!(
+                                "  [{}] verified: {}, failed: {}",
+                                module,
+                                verified.len(),
+                                failed.len()
+                            );
+                        }
+                        Err(err) => status!("  [{}] error: {:#}", module, err),
+                    }
+                }
+
+                results.lock().unwrap().push((module, outcome));
+            });
+        }
+    });
+
+    let mut merged_verified = Vec::new();
+    let mut merged_failed = Vec::new();
+    for (module, outcome) in results.into_inner().unwrap() {
+        let data = outcome.with_context(|| format!("module '{module}' failed to verify"))?;
+        if let Some(verification) = data.get("verification") {
+            if let Some(funcs) = verification.get("verified_functions").and_then(|v| v.as_array()) {
+                merged_verified.extend(funcs.iter().cloned());
+            }
+            if let Some(funcs) = verification.get("failed_functions").and_then(|v| v.as_array()) {
+                merged_failed.extend(funcs.iter().cloned());
+            }
+        }
+    }
+
+    let mut merged = HashMap::new();
+    merged.insert(
+        "verification".to_string(),
+        serde_json::json!({
+            "verified_functions": merged_verified,
+            "failed_functions": merged_failed,
+        }),
+    );
+    Ok(merged)
+}
+
+/// Guards `scip-atoms verify`'s invocation together with its immediately
+/// following intermediate-file cleanup below. The tool takes no
+/// `--root`/manifest-path argument -- it locates the crate it's verifying
+/// from its working directory, which every call site sets to
+/// `project_root` -- so its `data/verification_config.json` and
+/// `data/verification_output.txt` intermediates always land under
+/// `project_root/data/` regardless of which module is being verified.
+/// When fanned out across a worker pool, that `data/` directory is a
+/// resource shared by every in-flight invocation, so the run and its
+/// cleanup must happen one module at a time.
+static VERIFY_DATA_LOCK: Mutex<()> = Mutex::new(());
+
+/// Run scip-atoms verify and return the results. Always invoked with
+/// `project_root` as the working directory, matching every other call
+/// site -- scip-atoms has no `--root`/manifest-path flag, so cwd is how
+/// it finds the crate to verify.
 fn run_scip_verify(
     project_root: &Path,
     verification_path: &Path,
@@ -164,52 +597,48 @@ fn run_scip_verify(
     if let Some(module) = verify_only_module {
         args.push("--verify-only-module");
         args.push(module);
-        println!(
+        status!(
             "Running scip-atoms verify on {} (module: {})...",
             project_root.display(),
             module
         );
     } else {
-        println!(
+        status!(
             "Running scip-atoms verify on {}...",
             project_root.display()
         );
     }
 
-    let output = run_command("scip-atoms", &args, Some(project_root))?;
+    {
+        let _guard = VERIFY_DATA_LOCK.lock().unwrap();
+        run_command("scip-atoms", &args, Some(project_root))?;
+
+        // Clean up generated intermediate files. Held under the same lock
+        // as the invocation above, since another worker's in-flight run
+        // could otherwise have these files open concurrently.
+        for cleanup_file in [
+            "data/verification_config.json",
+            "data/verification_output.txt",
+        ] {
+            let cleanup_path = project_root.join(cleanup_file);
+            if cleanup_path.exists() {
+                let _ = std::fs::remove_file(&cleanup_path);
+            }
+        }
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        eprintln!("Error: scip-atoms verify failed.");
-        if !stderr.is_empty() {
-            eprintln!("{}", stderr);
+        let data_dir = project_root.join("data");
+        if data_dir.exists() && data_dir.is_dir() {
+            if std::fs::read_dir(&data_dir)?.next().is_none() {
+                let _ = std::fs::remove_dir(&data_dir);
+            }
         }
-        bail!("scip-atoms verify failed");
     }
 
-    println!(
+    status!(
         "Verification results saved to {}",
         verification_path.display()
     );
 
-    // Clean up generated intermediate files
-    for cleanup_file in [
-        "data/verification_config.json",
-        "data/verification_output.txt",
-    ] {
-        let cleanup_path = project_root.join(cleanup_file);
-        if cleanup_path.exists() {
-            let _ = std::fs::remove_file(&cleanup_path);
-        }
-    }
-
-    let data_dir = project_root.join("data");
-    if data_dir.exists() && data_dir.is_dir() {
-        if std::fs::read_dir(&data_dir)?.next().is_none() {
-            let _ = std::fs::remove_dir(&data_dir);
-        }
-    }
-
     let content = std::fs::read_to_string(verification_path)?;
     let verification: HashMap<String, Value> = serde_json::from_str(&content)?;
     Ok(verification)
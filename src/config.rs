@@ -0,0 +1,182 @@
+//! Project configuration.
+//!
+//! `create` writes `.verilib/config.toml`, recording the structure type,
+//! form, and root it was given so later subcommands (`atomize`, `specify`,
+//! `verify`) don't need them repeated on every invocation. The same file
+//! can be hand-edited to add an `[alias]` table of shorthand subcommand
+//! invocations, which `main` splices into argv before clap parses it.
+
+use crate::{StructureForm, StructureType};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Filename of the project config file, relative to `.verilib/`.
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// External tool constants shared across subcommands.
+pub mod constants {
+    /// Repo to point users at when `probe-verus` isn't installed.
+    pub const PROBE_VERUS_REPO: &str = "https://github.com/secure-foundations/probe-verus";
+    /// Repo to point users at when `scip-atoms` isn't installed.
+    pub const SCIP_ATOMS_REPO: &str = "https://github.com/secure-foundations/scip-atoms";
+    /// Prefix `probe-verus` atoms are filtered by before use.
+    pub const PROBE_PREFIX: &str = "verus:";
+    /// Prefix passed to `scip-atoms verify` identifying the SCIP indexer in use.
+    pub const SCIP_PREFIX: &str = "rust-analyzer";
+    /// `blueprint.json` `term-status` values that count as verified.
+    pub const BLUEPRINT_VERIFIED_STATUSES: [&str; 2] = ["formalized", "proved"];
+}
+
+/// Persisted and user-editable project configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    pub structure_type: String,
+    pub form: String,
+    pub structure_root: String,
+    /// User-defined command aliases, e.g. `check = "verify --verify-only-module core"`.
+    #[serde(default)]
+    pub alias: HashMap<String, String>,
+}
+
+impl Config {
+    /// Build a config to persist after a `create` run.
+    pub fn new(structure_type: StructureType, form: StructureForm, structure_root: &str) -> Self {
+        Config {
+            structure_type: structure_type.to_string(),
+            form: form.to_string(),
+            structure_root: structure_root.to_string(),
+            alias: HashMap::new(),
+        }
+    }
+
+    fn path(project_root: &Path) -> PathBuf {
+        project_root.join(".verilib").join(CONFIG_FILE_NAME)
+    }
+
+    /// Write the config to `.verilib/config.toml`, preserving any
+    /// hand-edited `[alias]` table already on disk.
+    pub fn save(&self, project_root: &Path) -> Result<PathBuf> {
+        let path = Self::path(project_root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut to_write = self.clone();
+        if to_write.alias.is_empty() {
+            if let Ok(existing) = Self::load(project_root) {
+                to_write.alias = existing.alias;
+            }
+        }
+
+        let content = toml::to_string_pretty(&to_write).context("Failed to serialize config.toml")?;
+        fs::write(&path, content)?;
+        Ok(path)
+    }
+
+    /// Load `.verilib/config.toml` written by `create`.
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let path = Self::path(project_root);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {} (run `create` first)", path.display()))?;
+        let config: Config = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(config)
+    }
+
+    pub fn get_structure_type(&self) -> Result<StructureType> {
+        match self.structure_type.as_str() {
+            "dalek-lite" => Ok(StructureType::DalekLite),
+            "blueprint" => Ok(StructureType::Blueprint),
+            other => bail!("Unknown structure_type '{}' in config.toml", other),
+        }
+    }
+
+    pub fn get_structure_form(&self) -> Result<StructureForm> {
+        match self.form.as_str() {
+            "json" => Ok(StructureForm::Json),
+            "files" => Ok(StructureForm::Files),
+            other => bail!("Unknown form '{}' in config.toml", other),
+        }
+    }
+
+    /// Resolve `create`'s `--type`/`--form`/`--root` flags against this
+    /// project's `.verilib/config.toml`, falling back to the file's
+    /// defaults for anything the CLI left unset. Explicit CLI flags always
+    /// win over the file.
+    pub fn resolve_create_args(
+        project_root: &Path,
+        structure_type: Option<StructureType>,
+        form: Option<StructureForm>,
+        root: Option<PathBuf>,
+    ) -> Result<(StructureType, StructureForm, Option<PathBuf>)> {
+        let defaults = Self::load(project_root).ok();
+
+        let structure_type = match structure_type {
+            Some(t) => t,
+            None => defaults
+                .as_ref()
+                .map(Config::get_structure_type)
+                .transpose()?
+                .context("--type is required (or set structure_type in .verilib/config.toml)")?,
+        };
+
+        let form = match form {
+            Some(f) => f,
+            None => match &defaults {
+                Some(config) => config.get_structure_form()?,
+                None => StructureForm::Json,
+            },
+        };
+
+        let root = root.or_else(|| defaults.map(|config| PathBuf::from(config.structure_root)));
+
+        Ok((structure_type, form, root))
+    }
+
+    /// Load the `[alias]` table from `.verilib/config.toml`, if the project
+    /// has one. Used by `main` to splice alias expansions into argv before
+    /// clap parses the command line.
+    pub fn load_aliases(project_root: &Path) -> HashMap<String, String> {
+        Self::load(project_root)
+            .map(|config| config.alias)
+            .unwrap_or_default()
+    }
+}
+
+/// Resolved `.verilib` file layout for a project.
+pub struct ConfigPaths {
+    pub config: Config,
+    pub verilib_path: PathBuf,
+    pub atoms_path: PathBuf,
+    pub structure_root: PathBuf,
+    pub structure_json_path: PathBuf,
+    pub structure_meta_path: PathBuf,
+    pub blueprint_json_path: PathBuf,
+    pub certs_specify_dir: PathBuf,
+    pub certs_verify_dir: PathBuf,
+}
+
+impl ConfigPaths {
+    /// Load `.verilib/config.toml` and resolve every other `.verilib` path
+    /// relative to `project_root`.
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let config = Config::load(project_root)?;
+        let verilib_path = project_root.join(".verilib");
+        let structure_root = project_root.join(&config.structure_root);
+
+        Ok(ConfigPaths {
+            structure_json_path: verilib_path.join("stubs.json"),
+            structure_meta_path: verilib_path.join("structure-meta.json"),
+            blueprint_json_path: verilib_path.join("blueprint.json"),
+            atoms_path: verilib_path.join("atoms.json"),
+            certs_specify_dir: verilib_path.join("certs").join("specify"),
+            certs_verify_dir: verilib_path.join("certs").join("verify"),
+            structure_root,
+            verilib_path,
+            config,
+        })
+    }
+}
@@ -0,0 +1,259 @@
+//! Verification-coverage report exporters (LCOV / Cobertura / JSON summary).
+//!
+//! `create` builds a structure map whose entries already carry enough
+//! proof/spec state to answer "is this specified?" / "is this proven?" --
+//! dalek-lite entries have `has-spec`/`has-proof`/`is-external-body`,
+//! blueprint entries have `type-status`/`term-status`. This module
+//! classifies every entry into those two booleans, groups them by source
+//! file (dalek-lite: `code-path`; blueprint: the node's own file), and
+//! renders the result in one of three CI-friendly formats.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::StructureType;
+
+/// Coverage report output format selectable via `create --report`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    #[value(name = "lcov")]
+    Lcov,
+    #[value(name = "cobertura")]
+    Cobertura,
+    #[value(name = "json-summary")]
+    JsonSummary,
+}
+
+/// One structure entry's classification for the coverage report.
+struct NodeStatus {
+    group: String,
+    code_line: u32,
+    specified: bool,
+    proven: bool,
+    is_external_body: bool,
+}
+
+/// Per-group (and grand total) tallies, plus the per-line hit data LCOV and
+/// Cobertura need. `code-line == 0` (unparsed links, or blueprint nodes,
+/// which have no source line at all) is excluded from `lines` but still
+/// counted in the other totals.
+#[derive(Default)]
+struct GroupTotals {
+    specified: usize,
+    proven: usize,
+    external: usize,
+    total: usize,
+    lines: Vec<(u32, bool)>,
+}
+
+/// Classify every structure entry as specified/proven/external, per
+/// `structure_type`'s node shape.
+fn classify(structure: &HashMap<String, Value>, structure_type: StructureType) -> Vec<NodeStatus> {
+    structure
+        .iter()
+        .map(|(file_path, entry)| match structure_type {
+            StructureType::DalekLite => NodeStatus {
+                group: entry
+                    .get("code-path")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(file_path)
+                    .to_string(),
+                code_line: entry.get("code-line").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
+                specified: entry.get("has-spec").and_then(|v| v.as_bool()).unwrap_or(false),
+                proven: entry.get("has-proof").and_then(|v| v.as_bool()).unwrap_or(false),
+                is_external_body: entry
+                    .get("is-external-body")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+            },
+            StructureType::Blueprint => {
+                let type_status = entry.get("type-status").and_then(|v| v.as_str()).unwrap_or("");
+                let term_status = entry.get("term-status").and_then(|v| v.as_str()).unwrap_or("");
+                NodeStatus {
+                    group: file_path.clone(),
+                    code_line: 0,
+                    specified: matches!(type_status, "stated" | "mathlib"),
+                    proven: matches!(term_status, "proved" | "fully-proved"),
+                    is_external_body: false,
+                }
+            }
+        })
+        .collect()
+}
+
+/// Group classified nodes by source file, in stable (sorted) order.
+fn group_nodes(nodes: &[NodeStatus]) -> BTreeMap<String, GroupTotals> {
+    let mut groups: BTreeMap<String, GroupTotals> = BTreeMap::new();
+
+    for node in nodes {
+        let totals = groups.entry(node.group.clone()).or_default();
+        totals.total += 1;
+        if node.specified {
+            totals.specified += 1;
+        }
+        if node.is_external_body {
+            // Assumed, not proven: tallied separately rather than counted
+            // toward the proven numerator.
+            totals.external += 1;
+        } else if node.proven {
+            totals.proven += 1;
+        }
+        if node.code_line != 0 {
+            totals.lines.push((node.code_line, node.proven && !node.is_external_body));
+        }
+    }
+
+    groups
+}
+
+fn pct(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (count as f64 / total as f64) * 100.0
+    }
+}
+
+fn sorted_lines(totals: &GroupTotals) -> Vec<(u32, bool)> {
+    let mut lines = totals.lines.clone();
+    lines.sort_by_key(|(line, _)| *line);
+    lines
+}
+
+/// Render an LCOV tracefile: one `SF`/`DA*`/`LF`/`LH` record per group,
+/// with a line marked hit iff its function is proven.
+fn render_lcov(groups: &BTreeMap<String, GroupTotals>) -> String {
+    let mut out = String::new();
+
+    for (path, totals) in groups {
+        out.push_str(&format!("SF:{}\n", path));
+
+        let lines = sorted_lines(totals);
+        for (line, proven) in &lines {
+            out.push_str(&format!("DA:{},{}\n", line, if *proven { 1 } else { 0 }));
+        }
+
+        out.push_str(&format!("LF:{}\n", lines.len()));
+        out.push_str(&format!("LH:{}\n", lines.iter().filter(|(_, proven)| *proven).count()));
+        out.push_str("end_of_record\n");
+    }
+
+    out
+}
+
+/// Render a Cobertura-style XML report, one `<class>` per group with a
+/// `line-rate` computed from proven/total lines.
+fn render_cobertura(groups: &BTreeMap<String, GroupTotals>) -> String {
+    let mut classes = String::new();
+    let mut total_lines = 0usize;
+    let mut total_hits = 0usize;
+
+    for (path, totals) in groups {
+        let lines = sorted_lines(totals);
+        let hits = lines.iter().filter(|(_, proven)| *proven).count();
+        total_lines += lines.len();
+        total_hits += hits;
+        let line_rate = if lines.is_empty() {
+            0.0
+        } else {
+            hits as f64 / lines.len() as f64
+        };
+
+        let mut line_elems = String::new();
+        for (line, proven) in &lines {
+            line_elems.push_str(&format!(
+                "        <line number=\"{}\" hits=\"{}\"/>\n",
+                line,
+                if *proven { 1 } else { 0 }
+            ));
+        }
+
+        classes.push_str(&format!(
+            "    <class name=\"{path}\" filename=\"{path}\" line-rate=\"{line_rate:.4}\">\n      <lines>\n{line_elems}      </lines>\n    </class>\n",
+        ));
+    }
+
+    let overall_rate = if total_lines == 0 {
+        0.0
+    } else {
+        total_hits as f64 / total_lines as f64
+    };
+
+    format!(
+        "<?xml version=\"1.0\"?>\n\
+         <coverage line-rate=\"{overall_rate:.4}\" lines-covered=\"{total_hits}\" lines-valid=\"{total_lines}\">\n\
+         \x20 <packages>\n\
+         \x20   <package name=\"verilib-structure\">\n\
+         \x20     <classes>\n\
+         {classes}\
+         \x20     </classes>\n\
+         \x20   </package>\n\
+         \x20 </packages>\n\
+         </coverage>\n",
+    )
+}
+
+/// Render a `{path: {specified, proven, total, ...}}` JSON summary plus a
+/// `total` grand-total entry.
+fn render_json_summary(groups: &BTreeMap<String, GroupTotals>) -> Result<String> {
+    let mut summary = serde_json::Map::new();
+    let mut grand = GroupTotals::default();
+
+    for (path, totals) in groups {
+        grand.specified += totals.specified;
+        grand.proven += totals.proven;
+        grand.external += totals.external;
+        grand.total += totals.total;
+
+        summary.insert(
+            path.clone(),
+            json!({
+                "specified": totals.specified,
+                "proven": totals.proven,
+                "external_body": totals.external,
+                "total": totals.total,
+                "spec_pct": pct(totals.specified, totals.total),
+                "proof_pct": pct(totals.proven, totals.total),
+            }),
+        );
+    }
+
+    summary.insert(
+        "total".to_string(),
+        json!({
+            "specified": grand.specified,
+            "proven": grand.proven,
+            "external_body": grand.external,
+            "total": grand.total,
+            "spec_pct": pct(grand.specified, grand.total),
+            "proof_pct": pct(grand.proven, grand.total),
+        }),
+    );
+
+    Ok(serde_json::to_string_pretty(&summary)?)
+}
+
+/// Classify and render `structure` in `format`, writing the result under
+/// `verilib_path`. Returns the written path.
+pub fn write_report(
+    structure: &HashMap<String, Value>,
+    structure_type: StructureType,
+    format: ReportFormat,
+    verilib_path: &Path,
+) -> Result<PathBuf> {
+    let groups = group_nodes(&classify(structure, structure_type));
+
+    let (file_name, content) = match format {
+        ReportFormat::Lcov => ("coverage.lcov", render_lcov(&groups)),
+        ReportFormat::Cobertura => ("coverage.xml", render_cobertura(&groups)),
+        ReportFormat::JsonSummary => ("coverage-summary.json", render_json_summary(&groups)?),
+    };
+
+    std::fs::create_dir_all(verilib_path)?;
+    let path = verilib_path.join(file_name);
+    std::fs::write(&path, content)?;
+
+    Ok(path)
+}
@@ -0,0 +1,237 @@
+//! Transitive verification status over the call/dependency graph.
+//!
+//! Local verification (`get_verification_results`,
+//! `get_blueprint_verification_results`) only reports whether a function's
+//! own proof passed. A function that verifies locally but calls an
+//! unverified helper isn't actually trustworthy, so this module propagates
+//! local pass/fail over the `dependencies` edges `atomize` records,
+//! condensing strongly connected components first so mutual recursion
+//! doesn't get stuck.
+
+use std::collections::{HashMap, HashSet};
+
+/// A name's status once local verification is propagated over its
+/// dependencies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationStatus {
+    /// Verifies locally and every transitive dependency is also fully verified.
+    Verified,
+    /// Fails its own local verification.
+    Failed,
+    /// Verifies locally, but a dependency (direct, or a fellow member of
+    /// its strongly connected component) does not.
+    Blocked { blocking: String },
+}
+
+impl VerificationStatus {
+    pub fn is_verified(&self) -> bool {
+        matches!(self, VerificationStatus::Verified)
+    }
+}
+
+/// Propagate local verification results over the dependency graph,
+/// producing a transitive status for every name reachable via
+/// `dependencies` (not just those in `verified_locally`/`failed_locally`).
+///
+/// A strongly connected component (mutual recursion) is fully verified
+/// only if every member verifies locally and every dependency external to
+/// the component is fully verified. Names referenced as a dependency but
+/// with no local verification result at all (e.g. an unanalyzed external
+/// symbol) are conservatively treated as failed, since nothing vouches for
+/// them.
+pub fn propagate_verification(
+    verified_locally: &HashSet<String>,
+    failed_locally: &HashSet<String>,
+    dependencies: &HashMap<String, Vec<String>>,
+) -> HashMap<String, VerificationStatus> {
+    let mut nodes: HashSet<String> = HashSet::new();
+    nodes.extend(verified_locally.iter().cloned());
+    nodes.extend(failed_locally.iter().cloned());
+    for (name, deps) in dependencies {
+        nodes.insert(name.clone());
+        nodes.extend(deps.iter().cloned());
+    }
+
+    let components = tarjan_scc(&nodes, dependencies);
+
+    let mut status: HashMap<String, VerificationStatus> = HashMap::new();
+    for component in components {
+        let component_set: HashSet<&String> = component.iter().collect();
+
+        let all_locally_verified = component.iter().all(|name| verified_locally.contains(name));
+
+        let external_deps: HashSet<&String> = component
+            .iter()
+            .flat_map(|name| dependencies.get(name).into_iter().flatten())
+            .filter(|dep| !component_set.contains(dep))
+            .collect();
+        let all_external_verified = external_deps
+            .iter()
+            .all(|dep| matches!(status.get(*dep), Some(VerificationStatus::Verified)));
+
+        if all_locally_verified && all_external_verified {
+            for name in &component {
+                status.insert(name.clone(), VerificationStatus::Verified);
+            }
+            continue;
+        }
+
+        for name in &component {
+            if !verified_locally.contains(name) {
+                status.insert(name.clone(), VerificationStatus::Failed);
+                continue;
+            }
+
+            // Locally verified but the component as a whole isn't: find
+            // the nearest thing holding it back — a cycle-mate that fails
+            // locally, or else a direct dependency that isn't fully verified.
+            let blocking = component
+                .iter()
+                .find(|mate| *mate != name && !verified_locally.contains(*mate))
+                .cloned()
+                .or_else(|| {
+                    dependencies
+                        .get(name)
+                        .into_iter()
+                        .flatten()
+                        .find(|dep| !matches!(status.get(*dep), Some(VerificationStatus::Verified)))
+                        .cloned()
+                })
+                .unwrap_or_else(|| "<unknown>".to_string());
+
+            status.insert(name.clone(), VerificationStatus::Blocked { blocking });
+        }
+    }
+
+    status
+}
+
+/// Tarjan's strongly-connected-components algorithm, implemented
+/// iteratively (an explicit stack of `(node, next child index)` frames) to
+/// avoid recursion depth limits on large dependency graphs.
+///
+/// Returns components in the order Tarjan completes them, which is a
+/// reverse topological order of the condensation: a component with no
+/// outgoing edges to another component (a leaf of the call graph) is
+/// completed, and so returned, before anything that depends on it. That's
+/// exactly the order `propagate_verification` needs to resolve statuses in.
+fn tarjan_scc(nodes: &HashSet<String>, edges: &HashMap<String, Vec<String>>) -> Vec<Vec<String>> {
+    let no_deps: Vec<String> = Vec::new();
+
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut lowlink: HashMap<String, usize> = HashMap::new();
+    let mut on_stack: HashSet<String> = HashSet::new();
+    let mut stack: Vec<String> = Vec::new();
+    let mut next_index = 0usize;
+    let mut components: Vec<Vec<String>> = Vec::new();
+
+    let mut roots: Vec<&String> = nodes.iter().collect();
+    roots.sort();
+
+    for root in roots {
+        if index.contains_key(root) {
+            continue;
+        }
+
+        let mut call_stack: Vec<(String, usize)> = vec![(root.clone(), 0)];
+        index.insert(root.clone(), next_index);
+        lowlink.insert(root.clone(), next_index);
+        next_index += 1;
+        stack.push(root.clone());
+        on_stack.insert(root.clone());
+
+        while let Some((node, pos)) = call_stack.last().cloned() {
+            let children = edges.get(&node).unwrap_or(&no_deps);
+
+            if pos < children.len() {
+                call_stack.last_mut().unwrap().1 += 1;
+                let child = children[pos].clone();
+
+                if !index.contains_key(&child) {
+                    index.insert(child.clone(), next_index);
+                    lowlink.insert(child.clone(), next_index);
+                    next_index += 1;
+                    stack.push(child.clone());
+                    on_stack.insert(child.clone());
+                    call_stack.push((child, 0));
+                } else if on_stack.contains(&child) {
+                    let child_index = index[&child];
+                    let node_low = lowlink[&node];
+                    lowlink.insert(node.clone(), node_low.min(child_index));
+                }
+            } else {
+                call_stack.pop();
+                if let Some((parent, _)) = call_stack.last() {
+                    let node_low = lowlink[&node];
+                    let parent_low = lowlink[parent];
+                    lowlink.insert(parent.clone(), parent_low.min(node_low));
+                }
+
+                if lowlink[&node] == index[&node] {
+                    let mut component = Vec::new();
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack.remove(&member);
+                        component.push(member.clone());
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutually_recursive_cycle_is_blocked_by_the_member_that_fails() {
+        // A and B call each other; A verifies locally but B doesn't, so
+        // neither can be fully Verified even though A's own proof passed.
+        let verified_locally: HashSet<String> = ["A".to_string()].into_iter().collect();
+        let failed_locally: HashSet<String> = ["B".to_string()].into_iter().collect();
+        let dependencies: HashMap<String, Vec<String>> = [
+            ("A".to_string(), vec!["B".to_string()]),
+            ("B".to_string(), vec!["A".to_string()]),
+        ]
+        .into_iter()
+        .collect();
+
+        let status = propagate_verification(&verified_locally, &failed_locally, &dependencies);
+
+        assert_eq!(status.get("B"), Some(&VerificationStatus::Failed));
+        assert!(!status.get("B").unwrap().is_verified());
+        match status.get("A") {
+            Some(VerificationStatus::Blocked { blocking }) => assert_eq!(blocking, "B"),
+            other => panic!("expected A to be Blocked on B, got {other:?}"),
+        }
+        assert!(!status.get("A").unwrap().is_verified());
+    }
+
+    #[test]
+    fn fully_verified_cycle_with_a_verified_external_dependency() {
+        // A and B call each other and both verify locally; C is an
+        // external dependency of A that's already fully Verified.
+        let verified_locally: HashSet<String> =
+            ["A".to_string(), "B".to_string(), "C".to_string()].into_iter().collect();
+        let failed_locally: HashSet<String> = HashSet::new();
+        let dependencies: HashMap<String, Vec<String>> = [
+            ("A".to_string(), vec!["B".to_string(), "C".to_string()]),
+            ("B".to_string(), vec!["A".to_string()]),
+        ]
+        .into_iter()
+        .collect();
+
+        let status = propagate_verification(&verified_locally, &failed_locally, &dependencies);
+
+        assert_eq!(status.get("A"), Some(&VerificationStatus::Verified));
+        assert_eq!(status.get("B"), Some(&VerificationStatus::Verified));
+        assert_eq!(status.get("C"), Some(&VerificationStatus::Verified));
+    }
+}
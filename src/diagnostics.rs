@@ -0,0 +1,120 @@
+//! Compiler-style annotated source snippets for verification/spec gaps.
+//!
+//! Wraps the `annotate-snippets` rendering model: a snippet carries the
+//! source text plus a starting line number, and one or more annotations
+//! given as byte-offset ranges within that source. We resolve a
+//! `(code_path, line_number)` pair (as produced by `utils::parse_github_link`)
+//! against the project tree and render a small labeled excerpt around the
+//! target line, falling back to a plain one-line message when the source
+//! can't be read.
+
+use annotate_snippets::{Annotation, AnnotationType, Renderer, Slice, Snippet};
+use std::path::Path;
+
+/// Severity of a diagnostic snippet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    fn annotation_type(self) -> AnnotationType {
+        match self {
+            Severity::Error => AnnotationType::Error,
+            Severity::Warning => AnnotationType::Warning,
+            Severity::Note => AnnotationType::Note,
+        }
+    }
+}
+
+/// How many lines of context to show above and below the target line.
+const CONTEXT_LINES: usize = 2;
+
+/// Render an annotated snippet pointing at `line_number` (1-indexed) in
+/// `code_path` (resolved relative to `project_root`), labeled with `label`.
+///
+/// Returns `None` if the source file cannot be read or the line number is
+/// out of range, so callers can fall back to plain text.
+pub fn render_snippet(
+    project_root: &Path,
+    code_path: &str,
+    line_number: u32,
+    severity: Severity,
+    label: &str,
+) -> Option<String> {
+    if line_number == 0 {
+        return None;
+    }
+
+    let source_path = project_root.join(code_path);
+    let content = std::fs::read_to_string(&source_path).ok()?;
+
+    let lines: Vec<&str> = content.lines().collect();
+    let target_idx = (line_number as usize).checked_sub(1)?;
+    if target_idx >= lines.len() {
+        return None;
+    }
+
+    let context_start = target_idx.saturating_sub(CONTEXT_LINES);
+    let context_end = (target_idx + CONTEXT_LINES + 1).min(lines.len());
+
+    // Byte offset of context_start within `content`, and of the target line
+    // relative to that, so the annotation range lines up with the slice we hand over.
+    let slice_start_offset: usize = lines[..context_start]
+        .iter()
+        .map(|l| l.len() + 1)
+        .sum();
+    let target_offset: usize = lines[context_start..target_idx]
+        .iter()
+        .map(|l| l.len() + 1)
+        .sum();
+    let target_len = lines[target_idx].len().max(1);
+
+    let slice_source = lines[context_start..context_end].join("\n");
+
+    let snippet = Snippet {
+        title: Some(Annotation {
+            label: Some(label),
+            id: None,
+            annotation_type: severity.annotation_type(),
+        }),
+        footer: vec![],
+        slices: vec![Slice {
+            source: &slice_source,
+            line_start: context_start + 1,
+            origin: Some(code_path),
+            fold: false,
+            annotations: vec![annotate_snippets::SourceAnnotation {
+                range: (target_offset, target_offset + target_len),
+                label,
+                annotation_type: severity.annotation_type(),
+            }],
+        }],
+    };
+
+    Some(Renderer::styled().render(snippet).to_string())
+}
+
+/// Render an annotated snippet, or a plain one-line fallback if the source
+/// cannot be resolved.
+pub fn render_or_fallback(
+    project_root: &Path,
+    code_path: &str,
+    line_number: u32,
+    severity: Severity,
+    label: &str,
+) -> String {
+    render_snippet(project_root, code_path, line_number, severity, label).unwrap_or_else(|| {
+        let severity_label = match severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        };
+        format!(
+            "{}: {} ({}:{})",
+            severity_label, label, code_path, line_number
+        )
+    })
+}
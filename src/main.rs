@@ -6,21 +6,44 @@
 //! - `atomize`  - Enrich structure files with metadata
 //! - `specify`  - Check specification status and manage spec certs
 //! - `verify`   - Run verification and manage verification certs
+//! - `fmt`      - Normalize structure files to their canonical form
+//! - `search`   - Full-text search over enriched structure entries
 
+mod atom_cache;
+mod atomize_manifest;
+mod certs;
 mod commands;
+mod coverage;
+mod search_index;
+mod site;
 mod config;
+mod dependency;
+mod diagnostics;
+mod trust;
 mod utils;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
+/// Subcommand names clap derives from `Commands`, used to tell a real
+/// subcommand apart from an alias when resolving argv[1].
+const KNOWN_SUBCOMMANDS: &[&str] = &["create", "atomize", "specify", "verify", "fmt", "search"];
+
 /// Unified CLI for verilib structure management
 #[derive(Parser)]
 #[command(name = "verilib-structure")]
 #[command(about = "CLI toolkit for managing formal verification workflows")]
 #[command(version)]
 struct Cli {
+    /// Print every spawned command before running it
+    #[arg(long, global = true, conflicts_with = "quiet")]
+    verbose: bool,
+
+    /// Suppress non-error output
+    #[arg(long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -33,17 +56,29 @@ enum Commands {
         #[arg(default_value = ".")]
         project_root: PathBuf,
 
-        /// Type of the source to analyze
+        /// Type of the source to analyze (default: from .verilib/config.toml)
         #[arg(long = "type", value_enum)]
-        structure_type: StructureType,
+        structure_type: Option<StructureType>,
 
-        /// Structure form: 'json' or 'files' (default: json)
-        #[arg(long, value_enum, default_value = "json")]
-        form: StructureForm,
+        /// Structure form: 'json' or 'files' (default: from .verilib/config.toml, else json)
+        #[arg(long, value_enum)]
+        form: Option<StructureForm>,
 
-        /// Root directory for structure files (default: .verilib)
+        /// Root directory for structure files (default: from .verilib/config.toml, else .verilib/structure)
         #[arg(long)]
         root: Option<PathBuf>,
+
+        /// Emit a verification-coverage report in this format after building the structure
+        #[arg(long, value_enum)]
+        report: Option<coverage::ReportFormat>,
+
+        /// Also render the structure as a static, offline-browsable HTML site in this directory
+        #[arg(long)]
+        html: Option<PathBuf>,
+
+        /// Also export the dependency structure as a Graphviz DOT file at this path
+        #[arg(long)]
+        dot: Option<PathBuf>,
     },
 
     /// Enrich structure files with metadata
@@ -51,6 +86,26 @@ enum Commands {
         /// Project root directory (default: current working directory)
         #[arg(default_value = ".")]
         project_root: PathBuf,
+
+        /// Re-sync structure files' code-name from source positions before enriching metadata (files form only)
+        #[arg(long)]
+        update_stubs: bool,
+
+        /// Re-run probe-verus even if sources are unchanged since the last atomize
+        #[arg(long)]
+        force: bool,
+
+        /// Re-anchor a code-name whose code-line drifted by up to N lines instead of dropping it (dalek-lite only)
+        #[arg(long, default_value_t = 0)]
+        line_tolerance: u32,
+
+        /// Report which structure entries have drifted or gone missing without writing metadata (dalek-lite files form only)
+        #[arg(long)]
+        check: bool,
+
+        /// Parse ```rust fences in each structure file's markdown body and emit a test harness pairing them with the extracted atom (dalek-lite files form only)
+        #[arg(long)]
+        generate_harness: bool,
     },
 
     /// Check specification status and manage spec certs
@@ -58,6 +113,22 @@ enum Commands {
         /// Project root directory (default: current working directory)
         #[arg(default_value = ".")]
         project_root: PathBuf,
+
+        /// Report uncertified functions and stale stubs.json 'specified' flags without writing; exits non-zero otherwise
+        #[arg(long)]
+        check: bool,
+
+        /// Certify every currently-uncertified function without the interactive menu
+        #[arg(long)]
+        all: bool,
+
+        /// Certify uncertified functions whose code-name or code-path matches this glob (repeatable)
+        #[arg(long)]
+        select: Vec<String>,
+
+        /// Certify uncertified functions whose code-name appears in this newline-delimited file
+        #[arg(long)]
+        from_file: Option<PathBuf>,
     },
 
     /// Run verification and manage verification certs
@@ -69,6 +140,39 @@ enum Commands {
         /// Only verify functions in this module (dalek-lite only)
         #[arg(long)]
         verify_only_module: Option<String>,
+
+        /// Keep running, re-verifying affected modules whenever source files change
+        #[arg(long)]
+        watch: bool,
+
+        /// Worker threads when fanning out per module (dalek-lite only); the scip-atoms invocations themselves run one at a time
+        #[arg(long, default_value_t = 1)]
+        jobs: usize,
+    },
+
+    /// Normalize structure files to their canonical form
+    Fmt {
+        /// Project root directory (default: current working directory)
+        #[arg(default_value = ".")]
+        project_root: PathBuf,
+
+        /// Check that files are already canonical without writing; exits non-zero otherwise
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Full-text search over enriched structure entries
+    Search {
+        /// Project root directory (default: current working directory)
+        #[arg(default_value = ".")]
+        project_root: PathBuf,
+
+        /// Search query (matched against display-name, code-module, code-path, code-name, dependencies, and content)
+        query: String,
+
+        /// Max number of results to print (default: 20)
+        #[arg(long)]
+        limit: Option<usize>,
     },
 }
 
@@ -106,8 +210,46 @@ impl std::fmt::Display for StructureForm {
     }
 }
 
+/// Resolve `args[1]` against the `[alias]` table in `.verilib/config.toml`
+/// (read from the current directory) and splice its expansion into the
+/// argument vector. Mirrors cargo's aliased-subcommand resolution: an
+/// alias only fires when the first argument isn't already a real
+/// subcommand or a flag.
+fn expand_alias(args: Vec<String>) -> Result<Vec<String>> {
+    let Some(first) = args.get(1) else {
+        return Ok(args);
+    };
+
+    if first.starts_with('-') || KNOWN_SUBCOMMANDS.contains(&first.as_str()) {
+        return Ok(args);
+    }
+
+    let cwd = std::env::current_dir().context("Failed to resolve current directory")?;
+    let aliases = config::Config::load_aliases(&cwd);
+
+    let Some(expansion) = aliases.get(first) else {
+        return Ok(args);
+    };
+
+    let mut expanded = Vec::with_capacity(args.len() + expansion.split_whitespace().count());
+    expanded.push(args[0].clone());
+    expanded.extend(expansion.split_whitespace().map(str::to_string));
+    expanded.extend(args.into_iter().skip(2));
+    Ok(expanded)
+}
+
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let args = expand_alias(std::env::args().collect())?;
+    let cli = Cli::parse_from(args);
+
+    let log_level = if cli.quiet {
+        utils::LogLevel::Quiet
+    } else if cli.verbose {
+        utils::LogLevel::Verbose
+    } else {
+        utils::LogLevel::Normal
+    };
+    utils::set_log_level(log_level);
 
     match cli.command {
         Commands::Create {
@@ -115,15 +257,34 @@ fn main() -> Result<()> {
             structure_type,
             form,
             root,
-        } => commands::create::run(project_root, structure_type, form, root),
+            report,
+            html,
+            dot,
+        } => {
+            let (structure_type, form, root) =
+                config::Config::resolve_create_args(&project_root, structure_type, form, root)?;
+            commands::create::run(project_root, structure_type, form, root, report, html, dot)
+        }
 
-        Commands::Atomize { project_root } => commands::atomize::run(project_root),
+        Commands::Atomize { project_root, update_stubs, force, line_tolerance, check, generate_harness } => {
+            commands::atomize::run(project_root, update_stubs, force, line_tolerance, check, generate_harness)
+        }
 
-        Commands::Specify { project_root } => commands::specify::run(project_root),
+        Commands::Specify { project_root, check, all, select, from_file } => {
+            commands::specify::run(project_root, check, all, select, from_file)
+        }
 
         Commands::Verify {
             project_root,
             verify_only_module,
-        } => commands::verify::run(project_root, verify_only_module),
+            watch,
+            jobs,
+        } => commands::verify::run(project_root, verify_only_module, watch, jobs),
+
+        Commands::Fmt { project_root, check } => commands::fmt::run(project_root, check),
+
+        Commands::Search { project_root, query, limit } => {
+            commands::search::run(project_root, query, limit)
+        }
     }
 }
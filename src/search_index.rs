@@ -0,0 +1,107 @@
+//! Client-side search index generation.
+//!
+//! Builds a small inverted index over every structure entry's identifier
+//! (`veri-name`/`code-name`, `veri:`-prefix stripped), file path, `kind`,
+//! and markdown `content`, and serializes it to `.verilib/search-index.json`
+//! so the HTML site (or any other client-side tooling) can do instant
+//! full-text/prefix search without a running `verilib-structure search`.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::StructureType;
+
+/// Matches name hits outweighing an incidental match in the body, same
+/// intent as `commands::search`'s field weights.
+const WEIGHT_NAME: f64 = 5.0;
+const WEIGHT_PATH: f64 = 2.0;
+const WEIGHT_KIND: f64 = 2.0;
+const WEIGHT_CONTENT: f64 = 1.0;
+
+#[derive(Serialize)]
+struct IndexedNode {
+    id: String,
+    name: String,
+    kind: String,
+    path: String,
+}
+
+#[derive(Serialize)]
+struct SearchIndexFile {
+    nodes: Vec<IndexedNode>,
+    index: HashMap<String, Vec<(usize, f64)>>,
+}
+
+/// Lowercase, split on non-alphanumeric runs.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase())
+        .collect()
+}
+
+/// Build the `{nodes, index}` search index over `structure` and write it
+/// to `.verilib/search-index.json` under `verilib_path`. Returns the
+/// written path.
+pub fn write_search_index(
+    structure: &HashMap<String, Value>,
+    structure_type: StructureType,
+    verilib_path: &Path,
+) -> Result<PathBuf> {
+    let name_field = match structure_type {
+        StructureType::Blueprint => "veri-name",
+        StructureType::DalekLite => "code-name",
+    };
+
+    // Sorted so node_idx is stable across runs (HashMap iteration order isn't).
+    let mut file_paths: Vec<&String> = structure.keys().collect();
+    file_paths.sort();
+
+    let mut nodes = Vec::with_capacity(file_paths.len());
+    let mut index: HashMap<String, Vec<(usize, f64)>> = HashMap::new();
+
+    for (node_idx, file_path) in file_paths.into_iter().enumerate() {
+        let entry = &structure[file_path];
+
+        let name = entry
+            .get(name_field)
+            .and_then(|v| v.as_str())
+            .unwrap_or(file_path)
+            .to_string();
+        let name_for_index = name.strip_prefix("veri:").unwrap_or(&name);
+        let kind = entry.get("kind").and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let path = entry
+            .get("code-path")
+            .and_then(|v| v.as_str())
+            .unwrap_or(file_path)
+            .to_string();
+        let content = entry.get("content").and_then(|v| v.as_str()).unwrap_or("");
+
+        let mut index_field = |text: &str, weight: f64| {
+            for token in tokenize(text) {
+                index.entry(token).or_default().push((node_idx, weight));
+            }
+        };
+
+        index_field(name_for_index, WEIGHT_NAME);
+        index_field(file_path, WEIGHT_PATH);
+        index_field(&kind, WEIGHT_KIND);
+        index_field(content, WEIGHT_CONTENT);
+
+        nodes.push(IndexedNode {
+            id: file_path.clone(),
+            name,
+            kind,
+            path,
+        });
+    }
+
+    let output_path = verilib_path.join("search-index.json");
+    std::fs::create_dir_all(verilib_path)?;
+    std::fs::write(&output_path, serde_json::to_string_pretty(&SearchIndexFile { nodes, index })?)?;
+
+    Ok(output_path)
+}
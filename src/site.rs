@@ -0,0 +1,335 @@
+//! Static, offline-browsable HTML site generation for a built structure.
+//!
+//! Renders one page per structure entry -- its markdown `content`, `kind`,
+//! and status badges color-coded exactly like the DOT shape/color mapping
+//! `commands::create::parse_node_element` uses for blueprint nodes -- plus
+//! an index page embedding the dependency graph as an SVG rendered by
+//! shelling out to `dot`, mirroring how `create` already shells out to
+//! `leanblueprint`. Dependency names (`veri:`-prefixed for blueprint,
+//! plain `code-name` for dalek-lite) are resolved back to their target
+//! page and hyperlinked where a target exists.
+
+use crate::utils::{run_command, status, write_files_parallel};
+use crate::StructureType;
+use anyhow::{Context, Result};
+use pulldown_cmark::{html, Parser as MarkdownParser};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Swap a structure entry's `.md` key for the page path it renders to.
+fn page_path(file_path: &str) -> String {
+    format!("{}.html", file_path.strip_suffix(".md").unwrap_or(file_path))
+}
+
+fn type_status_color(status: &str) -> &'static str {
+    match status {
+        "stated" => "green",
+        "can-state" => "blue",
+        "not-ready" => "#FFAA33",
+        "mathlib" => "darkgreen",
+        _ => "gray",
+    }
+}
+
+fn term_status_color(status: &str) -> &'static str {
+    match status {
+        "proved" => "#9CEC8B",
+        "defined" => "#B0ECA3",
+        "can-prove" => "#A3D6FF",
+        "fully-proved" => "#1CAC78",
+        _ => "gray",
+    }
+}
+
+fn badge(label: &str, color: &str) -> String {
+    format!(
+        r#"<span class="badge" style="background:{}">{}</span>"#,
+        color,
+        html_escape(label)
+    )
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The node's identifier field (used to resolve dependency edges),
+/// `code-name` for dalek-lite and `veri-name` for blueprint.
+fn name_field(structure_type: StructureType) -> &'static str {
+    match structure_type {
+        StructureType::DalekLite => "code-name",
+        StructureType::Blueprint => "veri-name",
+    }
+}
+
+/// Render this entry's status badges from the fields `create` populates:
+/// `type-status`/`term-status` for blueprint, `has-spec`/`has-proof`/
+/// `is-external-body` for dalek-lite.
+fn status_badges(entry: &Value, structure_type: StructureType) -> String {
+    match structure_type {
+        StructureType::Blueprint => {
+            let type_status = entry.get("type-status").and_then(|v| v.as_str()).unwrap_or("unknown");
+            let term_status = entry.get("term-status").and_then(|v| v.as_str()).unwrap_or("unknown");
+            format!(
+                "{}{}",
+                badge(type_status, type_status_color(type_status)),
+                badge(term_status, term_status_color(term_status))
+            )
+        }
+        StructureType::DalekLite => {
+            let has_spec = entry.get("has-spec").and_then(|v| v.as_bool()).unwrap_or(false);
+            let has_proof = entry.get("has-proof").and_then(|v| v.as_bool()).unwrap_or(false);
+            let is_external = entry.get("is-external-body").and_then(|v| v.as_bool()).unwrap_or(false);
+
+            let mut badges = String::new();
+            badges.push_str(&badge(
+                if has_spec { "specified" } else { "unspecified" },
+                if has_spec { "#9CEC8B" } else { "#FFAA33" },
+            ));
+            if is_external {
+                badges.push_str(&badge("external-body", "gray"));
+            } else {
+                badges.push_str(&badge(
+                    if has_proof { "proven" } else { "unproven" },
+                    if has_proof { "#9CEC8B" } else { "#FFAA33" },
+                ));
+            }
+            badges
+        }
+    }
+}
+
+/// Every dependency list a node can carry, merged and deduplicated in
+/// encounter order.
+fn dependency_names(entry: &Value) -> Vec<String> {
+    let mut names = Vec::new();
+    for field in ["dependencies", "type-dependencies", "term-dependencies"] {
+        if let Some(deps) = entry.get(field).and_then(|v| v.as_array()) {
+            for dep in deps {
+                if let Some(s) = dep.as_str() {
+                    if !names.iter().any(|n| n == s) {
+                        names.push(s.to_string());
+                    }
+                }
+            }
+        }
+    }
+    names
+}
+
+/// Render a dependency name as a link to its target page, or as plain
+/// text if the target isn't in this structure. `depth` is the `../` prefix
+/// leading from the linking page back to the site root.
+fn render_dependency_link(name: &str, name_to_page: &HashMap<String, String>, depth: &str) -> String {
+    match name_to_page.get(name) {
+        Some(page) => format!(r#"<li><a href="{}{}">{}</a></li>"#, depth, page, html_escape(name)),
+        None => format!("<li>{}</li>", html_escape(name)),
+    }
+}
+
+fn render_node_page(
+    file_path: &str,
+    entry: &Value,
+    structure_type: StructureType,
+    name_to_page: &HashMap<String, String>,
+) -> String {
+    let title = entry
+        .get(name_field(structure_type))
+        .and_then(|v| v.as_str())
+        .unwrap_or(file_path);
+
+    let kind = entry.get("kind").and_then(|v| v.as_str()).unwrap_or("");
+
+    let content_markdown = entry.get("content").and_then(|v| v.as_str()).unwrap_or("");
+    let mut content_html = String::new();
+    html::push_html(&mut content_html, MarkdownParser::new(content_markdown));
+
+    let depth = "../".repeat(file_path.matches('/').count());
+    let deps_html = dependency_names(entry)
+        .iter()
+        .map(|name| render_dependency_link(name, name_to_page, &depth))
+        .collect::<String>();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<link rel="stylesheet" href="{depth}style.css">
+</head>
+<body>
+<p><a href="{depth}index.html">&larr; index</a></p>
+<h1>{title}</h1>
+<p>{kind_badge}{status}</p>
+<div class="content">{content_html}</div>
+<h2>Dependencies</h2>
+<ul class="dependencies">{deps_html}</ul>
+</body>
+</html>
+"#,
+        title = html_escape(title),
+        depth = depth,
+        kind_badge = if kind.is_empty() { String::new() } else { badge(kind, "gray") },
+        status = status_badges(entry, structure_type),
+        content_html = content_html,
+        deps_html = deps_html,
+    )
+}
+
+/// Build a DOT source for the dependency graph, with each node's `URL`
+/// attribute set to its page path so `dot -Tsvg` emits clickable links.
+fn render_dot(structure: &HashMap<String, Value>, structure_type: StructureType, name_to_page: &HashMap<String, String>) -> String {
+    let mut dot = String::from("digraph structure {\nrankdir=LR;\nnode [shape=box];\n");
+
+    for (file_path, entry) in structure {
+        let name = entry
+            .get(name_field(structure_type))
+            .and_then(|v| v.as_str())
+            .unwrap_or(file_path);
+        let page = page_path(file_path);
+        dot.push_str(&format!(
+            "  \"{name}\" [URL=\"{page}\", tooltip=\"{name}\"];\n",
+            name = name.replace('"', "'"),
+            page = page,
+        ));
+    }
+
+    for entry in structure.values() {
+        let source = entry
+            .get(name_field(structure_type))
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        for dep in dependency_names(entry) {
+            if name_to_page.contains_key(&dep) {
+                dot.push_str(&format!(
+                    "  \"{}\" -> \"{}\";\n",
+                    source.replace('"', "'"),
+                    dep.replace('"', "'")
+                ));
+            }
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Render the dependency graph to `graph.svg` under `output_root` by
+/// shelling out to `dot`. Returns `false` (and leaves no SVG behind) if
+/// the `dot` binary isn't available, so the index page can fall back to
+/// a plain node list.
+fn render_graph_svg(dot_source: &str, output_root: &Path) -> Result<bool> {
+    if which::which("dot").is_err() {
+        eprintln!("Warning: 'dot' (Graphviz) not found in PATH, skipping dependency graph render");
+        return Ok(false);
+    }
+
+    let dot_path = output_root.join("graph.dot");
+    std::fs::write(&dot_path, dot_source)?;
+
+    run_command(
+        "dot",
+        &["-Tsvg", "-o", "graph.svg", "graph.dot"],
+        Some(output_root),
+    )?;
+
+    Ok(true)
+}
+
+fn render_index_page(structure: &HashMap<String, Value>, structure_type: StructureType, has_graph: bool) -> String {
+    let mut rows = structure
+        .iter()
+        .map(|(file_path, entry)| {
+            let name = entry
+                .get(name_field(structure_type))
+                .and_then(|v| v.as_str())
+                .unwrap_or(file_path);
+            format!(
+                r#"<li><a href="{page}">{name}</a> {status}</li>"#,
+                page = page_path(file_path),
+                name = html_escape(name),
+                status = status_badges(entry, structure_type),
+            )
+        })
+        .collect::<Vec<_>>();
+    rows.sort();
+
+    let graph_html = if has_graph {
+        r#"<object type="image/svg+xml" data="graph.svg"></object>"#.to_string()
+    } else {
+        "<p><em>Graphviz 'dot' not found; dependency graph not rendered.</em></p>".to_string()
+    };
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Structure index</title>
+<link rel="stylesheet" href="style.css">
+</head>
+<body>
+<h1>Structure index</h1>
+<h2>Dependency graph</h2>
+{graph_html}
+<h2>All nodes</h2>
+<ul class="index">{rows}</ul>
+</body>
+</html>
+"#,
+        graph_html = graph_html,
+        rows = rows.join("\n"),
+    )
+}
+
+const STYLE_CSS: &str = "body { font-family: sans-serif; max-width: 60rem; margin: 2rem auto; }\n\
+.badge { display: inline-block; padding: 0.1em 0.5em; margin-right: 0.3em; border-radius: 0.3em; font-size: 0.8em; color: #111; }\n\
+ul.index, ul.dependencies { padding-left: 1.2rem; }\n\
+object { width: 100%; border: 1px solid #ccc; }\n";
+
+/// Render `structure` as a static HTML site under `output_root`: one page
+/// per entry, an `index.html` embedding the dependency graph, and a
+/// shared `style.css`.
+pub fn generate_structure_html(
+    structure: &HashMap<String, Value>,
+    structure_type: StructureType,
+    output_root: &Path,
+) -> Result<()> {
+    std::fs::create_dir_all(output_root).context("Failed to create HTML output directory")?;
+    std::fs::write(output_root.join("style.css"), STYLE_CSS)?;
+
+    let name_to_page: HashMap<String, String> = structure
+        .iter()
+        .filter_map(|(file_path, entry)| {
+            entry
+                .get(name_field(structure_type))
+                .and_then(|v| v.as_str())
+                .map(|name| (name.to_string(), page_path(file_path)))
+        })
+        .collect();
+
+    // Each node's page is independent, so fan the writes out across a
+    // worker pool the same way `create::generate_structure_files` does.
+    let items: Vec<(String, Value)> = structure.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    let page_count = write_files_parallel(items, |file_path, entry| {
+        let page = render_node_page(file_path, &entry, structure_type, &name_to_page);
+        let page_output_path = output_root.join(page_path(file_path));
+        if let Some(parent) = page_output_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&page_output_path, page)?;
+        Ok(None)
+    })?;
+
+    let dot_source = render_dot(structure, structure_type, &name_to_page);
+    let has_graph = render_graph_svg(&dot_source, output_root)?;
+
+    std::fs::write(output_root.join("index.html"), render_index_page(structure, structure_type, has_graph))?;
+
+    status!("Generated {} HTML pages in {}", page_count, output_root.display());
+    Ok(())
+}
@@ -0,0 +1,221 @@
+//! Web-of-trust acceptance policy for signed verification certs.
+//!
+//! Trusted verifier keys live in `.verilib/trust.toml`. Each key carries a
+//! trust weight (0-120, where 120 is fully trusted and 60 is "marginal" in
+//! the classic web-of-trust sense, so two marginal signers suffice) and an
+//! optional delegation depth letting it vouch for other keys up to that
+//! many hops. A cert is accepted once the summed weight of its valid,
+//! trust-graph-reachable signatures reaches `threshold`.
+
+use anyhow::{Context, Result};
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Weight of a fully-trusted key.
+pub const FULLY_TRUSTED_WEIGHT: u32 = 120;
+/// Weight of a "marginal" key; two marginal signatures reach [`FULLY_TRUSTED_WEIGHT`].
+pub const MARGINAL_WEIGHT: u32 = 60;
+
+/// One entry in `.verilib/trust.toml`'s `[[key]]` table.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrustedKeyConfig {
+    /// Hex-encoded Ed25519 public key.
+    pub public_key: String,
+    /// Human-readable label for the verifier, e.g. a name or email.
+    #[serde(default)]
+    pub name: String,
+    /// Trust weight this key contributes directly (0-120).
+    pub weight: u32,
+    /// How many hops of key delegation this key may vouch for. 0 = may not delegate.
+    #[serde(default)]
+    pub delegation_depth: u32,
+    /// Other keys (hex-encoded) this key has delegated trust to, each
+    /// inheriting this key's weight at one less `delegation_depth`.
+    #[serde(default)]
+    pub delegates: Vec<String>,
+}
+
+/// Web-of-trust acceptance policy: a threshold and the set of root-trusted keys.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TrustConfig {
+    /// Summed weight a cert's valid signatures must reach to be accepted.
+    pub threshold: u32,
+    #[serde(default, rename = "key")]
+    pub keys: Vec<TrustedKeyConfig>,
+}
+
+impl TrustConfig {
+    /// Load the web-of-trust policy from `.verilib/trust.toml`.
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let path = project_root.join(".verilib").join("trust.toml");
+        let content = std::fs::read_to_string(&path).with_context(|| {
+            format!(
+                "Failed to read {} (no trust policy configured for this project)",
+                path.display()
+            )
+        })?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Walk delegation edges depth-first to compute every reachable
+    /// key's effective trust weight, starting from the root keys listed
+    /// directly in the config. A delegated key inherits the weight of the
+    /// key that vouched for it.
+    fn effective_weights(&self) -> HashMap<String, u32> {
+        let mut weights: HashMap<String, u32> = HashMap::new();
+        let mut frontier: Vec<(String, u32, u32)> = Vec::new();
+
+        for key in &self.keys {
+            weights.insert(key.public_key.clone(), key.weight);
+            frontier.push((key.public_key.clone(), key.weight, key.delegation_depth));
+        }
+
+        while let Some((key_id, weight, depth)) = frontier.pop() {
+            if depth == 0 {
+                continue;
+            }
+            let Some(delegator) = self.keys.iter().find(|k| k.public_key == key_id) else {
+                continue;
+            };
+            for delegate in &delegator.delegates {
+                if weights.contains_key(delegate) {
+                    continue;
+                }
+                weights.insert(delegate.clone(), weight);
+                frontier.push((delegate.clone(), weight, depth - 1));
+            }
+        }
+
+        weights
+    }
+
+    /// Trust weight reachable for `public_key`, or 0 if it isn't in the
+    /// trust graph at all.
+    pub fn weight_of(&self, public_key: &str) -> u32 {
+        self.effective_weights()
+            .get(public_key)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// Canonical bytes a cert signature is computed over: binds the signature
+/// to a specific function, the source state it was issued against, who is
+/// vouching for it, and when.
+pub fn signing_payload(
+    scip_name: &str,
+    verifier_key_id: &str,
+    source_hash: &str,
+    timestamp_rfc3339: &str,
+) -> Vec<u8> {
+    format!("{scip_name}\0{verifier_key_id}\0{source_hash}\0{timestamp_rfc3339}").into_bytes()
+}
+
+/// Verify a detached signature against its payload. Returns `false` (never
+/// errors) on malformed key/signature hex so callers can treat any
+/// unparseable signature as simply invalid.
+pub fn verify_signature(public_key_hex: &str, payload: &[u8], signature_hex: &str) -> bool {
+    let Ok(key_bytes) = hex::decode(public_key_hex) else {
+        return false;
+    };
+    let Ok(key_bytes): std::result::Result<[u8; 32], _> = key_bytes.try_into() else {
+        return false;
+    };
+    let Ok(verifying_key) = VerifyingKey::from_bytes(&key_bytes) else {
+        return false;
+    };
+
+    let Ok(sig_bytes) = hex::decode(signature_hex) else {
+        return false;
+    };
+    let Ok(sig_bytes): std::result::Result<[u8; 64], _> = sig_bytes.try_into() else {
+        return false;
+    };
+    let signature = ed25519_dalek::Signature::from_bytes(&sig_bytes);
+
+    verifying_key.verify(payload, &signature).is_ok()
+}
+
+/// Hex-encoded public key identifying a signing key's owner.
+pub fn key_id(signing_key: &SigningKey) -> String {
+    hex::encode(signing_key.verifying_key().to_bytes())
+}
+
+/// Load this machine's Ed25519 signing key, used to attest verification
+/// results when creating certs.
+///
+/// Checked in order: the `VERILIB_SIGNING_KEY` environment variable (a
+/// hex-encoded 32-byte seed), then `.verilib/signer.key` under the project
+/// root.
+pub fn load_signing_key(project_root: &Path) -> Result<SigningKey> {
+    let hex_seed = match std::env::var("VERILIB_SIGNING_KEY") {
+        Ok(value) => value,
+        Err(_) => {
+            let path = project_root.join(".verilib").join("signer.key");
+            std::fs::read_to_string(&path).with_context(|| {
+                format!(
+                    "No signing key found. Set VERILIB_SIGNING_KEY or create {}",
+                    path.display()
+                )
+            })?
+        }
+    };
+
+    let seed_bytes = hex::decode(hex_seed.trim()).context("Signing key must be hex-encoded")?;
+    let seed: [u8; 32] = seed_bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Signing key must be a 32-byte Ed25519 seed"))?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+/// Sign `payload` with `signing_key`, hex-encoding the detached signature.
+pub fn sign(signing_key: &SigningKey, payload: &[u8]) -> String {
+    hex::encode(signing_key.sign(payload).to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(public_key: &str, weight: u32, delegation_depth: u32, delegates: &[&str]) -> TrustedKeyConfig {
+        TrustedKeyConfig {
+            public_key: public_key.to_string(),
+            name: String::new(),
+            weight,
+            delegation_depth,
+            delegates: delegates.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn two_hop_delegation_reaches_a_leaf_key_with_no_entry_of_its_own() {
+        // A is a root-trusted key that vouches for B, which in turn
+        // vouches for C. C has no entry of its own, so it's only
+        // reachable (and only weighted) via B's delegation.
+        let config = TrustConfig {
+            threshold: 100,
+            keys: vec![
+                key("A", FULLY_TRUSTED_WEIGHT, 2, &["B"]),
+                key("B", MARGINAL_WEIGHT, 1, &["C"]),
+            ],
+        };
+
+        assert_eq!(config.weight_of("A"), FULLY_TRUSTED_WEIGHT);
+        assert_eq!(config.weight_of("B"), MARGINAL_WEIGHT);
+        assert_eq!(config.weight_of("C"), MARGINAL_WEIGHT);
+    }
+
+    #[test]
+    fn untrusted_key_not_in_the_trust_graph_has_zero_weight() {
+        // A revoked or never-trusted signer simply has no entry (directly
+        // or by delegation) anywhere in the config.
+        let config = TrustConfig {
+            threshold: 100,
+            keys: vec![key("A", FULLY_TRUSTED_WEIGHT, 1, &["B"])],
+        };
+
+        assert_eq!(config.weight_of("revoked-signer"), 0);
+    }
+}
@@ -1,36 +1,59 @@
 //! Utility functions for verilib structure.
 
-use crate::config::constants::PROBE_VERUS_REPO;
+use crate::config::constants::{PROBE_VERUS_REPO, SCIP_ATOMS_REPO};
 use crate::{StructureForm, StructureType};
 use anyhow::{bail, Context, Result};
-use chrono::{DateTime, Utc};
-use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::{self, BufRead, Write};
 use std::path::Path;
 use std::process::Command;
+use std::sync::OnceLock;
+
+/// Verbosity level for spawned commands and diagnostic output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    /// Status output is suppressed; errors and warnings, which go to
+    /// stderr, are still printed.
+    Quiet,
+    /// Default output.
+    Normal,
+    /// Print every spawned command before running it.
+    Verbose,
+}
+
+static LOG_LEVEL: OnceLock<LogLevel> = OnceLock::new();
 
-/// Certificate data stored in cert files
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Cert {
-    pub timestamp: DateTime<Utc>,
+/// Set the global log level. Should be called once, early in `main`.
+pub fn set_log_level(level: LogLevel) {
+    // Ignore if already set (e.g. in tests); the first call wins.
+    let _ = LOG_LEVEL.set(level);
 }
 
-/// Encode an identifier for use as a filename.
-///
-/// Uses URL percent-encoding to replace special characters like '/', ':', '#', etc.
-pub fn encode_name(name: &str) -> String {
-    utf8_percent_encode(name, NON_ALPHANUMERIC).to_string()
+/// Get the current global log level, defaulting to `Normal` if unset.
+pub fn log_level() -> LogLevel {
+    *LOG_LEVEL.get().unwrap_or(&LogLevel::Normal)
 }
 
-/// Decode a filename back to an identifier.
-pub fn decode_name(encoded: &str) -> String {
-    percent_decode_str(encoded)
-        .decode_utf8_lossy()
-        .to_string()
+/// Like `println!`, but suppressed when `--quiet` is set. Use this in place
+/// of raw `println!` for informational/status output, so quiet mode
+/// leaves only what goes to stderr. Not for interactive
+/// prompts (e.g. `display_menu`), which must print regardless of log level.
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if $crate::utils::log_level() != $crate::utils::LogLevel::Quiet {
+            println!($($arg)*);
+        }
+    };
 }
+pub(crate) use status;
+
+// Cert creation/lookup (including provenance and staleness) lives in
+// `crate::certs`; re-exported here since most callers reach it via `utils`.
+pub use crate::certs::{
+    create_cert, decode_name, delete_cert, encode_name, get_existing_certs, Cert,
+};
 
 /// Check if probe-verus is installed
 pub fn check_probe_verus_installed() -> bool {
@@ -57,57 +80,24 @@ pub fn check_leanblueprint_installed() -> bool {
     which::which("leanblueprint").is_ok()
 }
 
-/// Get the set of identifiers that already have certs.
-pub fn get_existing_certs(certs_dir: &Path) -> Result<HashSet<String>> {
-    let mut existing = HashSet::new();
-
-    if !certs_dir.exists() {
-        return Ok(existing);
-    }
-
-    for entry in std::fs::read_dir(certs_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.extension().map_or(false, |ext| ext == "json") {
-            if let Some(stem) = path.file_stem() {
-                let encoded_name = stem.to_string_lossy();
-                let name = decode_name(&encoded_name);
-                existing.insert(name);
-            }
-        }
-    }
-
-    Ok(existing)
-}
-
-/// Create a cert file for a function.
-pub fn create_cert(certs_dir: &Path, name: &str) -> Result<std::path::PathBuf> {
-    std::fs::create_dir_all(certs_dir)?;
-
-    let encoded_name = encode_name(name);
-    let cert_path = certs_dir.join(format!("{}.json", encoded_name));
-
-    let cert = Cert {
-        timestamp: Utc::now(),
-    };
-
-    let content = serde_json::to_string_pretty(&cert)?;
-    std::fs::write(&cert_path, content)?;
-
-    Ok(cert_path)
+/// Check if scip-atoms is installed
+pub fn check_scip_atoms_installed() -> bool {
+    which::which("scip-atoms").is_ok()
 }
 
-/// Delete a cert file for a function.
-pub fn delete_cert(certs_dir: &Path, name: &str) -> Result<Option<std::path::PathBuf>> {
-    let encoded_name = encode_name(name);
-    let cert_path = certs_dir.join(format!("{}.json", encoded_name));
-
-    if cert_path.exists() {
-        std::fs::remove_file(&cert_path)?;
-        Ok(Some(cert_path))
-    } else {
-        Ok(None)
+/// Check if scip-atoms is installed, exit with instructions if not.
+pub fn check_scip_atoms_or_exit() -> Result<()> {
+    if !check_scip_atoms_installed() {
+        eprintln!("Error: scip-atoms is not installed.");
+        eprintln!("Please visit {} for installation instructions.", SCIP_ATOMS_REPO);
+        eprintln!();
+        eprintln!("Quick install:");
+        eprintln!("  git clone {}", SCIP_ATOMS_REPO);
+        eprintln!("  cd scip-atoms");
+        eprintln!("  cargo install --path .");
+        bail!("scip-atoms not installed");
     }
+    Ok(())
 }
 
 /// Get the set of identifier names from the structure.
@@ -165,6 +155,233 @@ pub fn get_structure_names(
     Ok(names)
 }
 
+/// Get a map from identifier name to its `(code-path, code-line)` location,
+/// for entries where both fields are present. Used to render annotated
+/// snippets pointing at the source of a given structure entry.
+pub fn get_structure_code_locations(
+    structure_type: StructureType,
+    structure_form: StructureForm,
+    structure_root: &Path,
+    structure_json_path: &Path,
+) -> Result<HashMap<String, (String, u32)>> {
+    let name_field = match structure_type {
+        StructureType::Blueprint => "veri-name",
+        StructureType::DalekLite => "code-name",
+    };
+
+    let mut locations = HashMap::new();
+
+    let mut record = |entry: &Value| {
+        let name = entry.get(name_field).and_then(|v| v.as_str());
+        let code_path = entry.get("code-path").and_then(|v| v.as_str());
+        let code_line = entry.get("code-line").and_then(|v| v.as_u64());
+        if let (Some(name), Some(code_path), Some(code_line)) = (name, code_path, code_line) {
+            locations.insert(name.to_string(), (code_path.to_string(), code_line as u32));
+        }
+    };
+
+    match structure_form {
+        StructureForm::Json => {
+            if !structure_json_path.exists() {
+                return Ok(locations);
+            }
+            let content = std::fs::read_to_string(structure_json_path)?;
+            let structure: HashMap<String, Value> = serde_json::from_str(&content)?;
+            for entry in structure.values() {
+                record(entry);
+            }
+        }
+        StructureForm::Files => {
+            if !structure_root.exists() {
+                return Ok(locations);
+            }
+            for entry in walkdir::WalkDir::new(structure_root)
+                .into_iter()
+                .filter_map(|e| e.ok())
+            {
+                let path = entry.path();
+                if path.extension().map_or(false, |ext| ext == "md") {
+                    if let Ok(frontmatter) = parse_frontmatter(path) {
+                        record(&json_from_frontmatter(&frontmatter));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(locations)
+}
+
+/// Get a map from identifier name to the names it depends on, as recorded
+/// in the structure's `dependencies` field by `atomize`. Used to propagate
+/// verification status transitively over the call graph.
+pub fn get_structure_dependencies(
+    structure_type: StructureType,
+    structure_form: StructureForm,
+    structure_root: &Path,
+    structure_json_path: &Path,
+) -> Result<HashMap<String, Vec<String>>> {
+    let name_field = match structure_type {
+        StructureType::Blueprint => "veri-name",
+        StructureType::DalekLite => "code-name",
+    };
+
+    let mut dependencies = HashMap::new();
+
+    let mut record = |entry: &Value| {
+        let Some(name) = entry.get(name_field).and_then(|v| v.as_str()) else {
+            return;
+        };
+        let deps = entry
+            .get("dependencies")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|d| d.as_str().map(str::to_string)).collect())
+            .unwrap_or_default();
+        dependencies.insert(name.to_string(), deps);
+    };
+
+    match structure_form {
+        StructureForm::Json => {
+            if !structure_json_path.exists() {
+                return Ok(dependencies);
+            }
+            let content = std::fs::read_to_string(structure_json_path)?;
+            let structure: HashMap<String, Value> = serde_json::from_str(&content)?;
+            for entry in structure.values() {
+                record(entry);
+            }
+        }
+        StructureForm::Files => {
+            if !structure_root.exists() {
+                return Ok(dependencies);
+            }
+            for entry in walkdir::WalkDir::new(structure_root)
+                .into_iter()
+                .filter_map(|e| e.ok())
+            {
+                let path = entry.path();
+                if path.extension().map_or(false, |ext| ext == "md") {
+                    if let Ok(frontmatter) = parse_frontmatter(path) {
+                        record(&json_from_frontmatter(&frontmatter));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(dependencies)
+}
+
+/// Get the full raw structure entry for each identifier name, keyed by
+/// `code-name`/`veri-name`. Unlike the narrower accessors above, this keeps
+/// every field an entry carries (`display-name`, `code-module`,
+/// `dependencies`, etc.), which `search` needs to tokenize.
+///
+/// Each entry also carries a synthetic `"__file_path"` key — the
+/// structure.json dict key for the `Json` form, or the `.md` file's path
+/// for the `Files` form — so a caller can locate the entry's file without
+/// a second traversal; this key is not part of any on-disk frontmatter.
+/// For the `Files` form, fields only materialized by `atomize` into the
+/// sibling `.meta.verilib` file (`code-module`, `dependencies`,
+/// `display-name`, `code-lines`) are merged in from there when present.
+pub fn get_structure_entries(
+    structure_type: StructureType,
+    structure_form: StructureForm,
+    structure_root: &Path,
+    structure_json_path: &Path,
+) -> Result<HashMap<String, Value>> {
+    let name_field = match structure_type {
+        StructureType::Blueprint => "veri-name",
+        StructureType::DalekLite => "code-name",
+    };
+
+    let mut entries = HashMap::new();
+
+    match structure_form {
+        StructureForm::Json => {
+            if !structure_json_path.exists() {
+                return Ok(entries);
+            }
+            let content = std::fs::read_to_string(structure_json_path)?;
+            let structure: HashMap<String, Value> = serde_json::from_str(&content)?;
+            for (file_path, mut entry) in structure {
+                let Some(name) = entry.get(name_field).and_then(|v| v.as_str()).map(str::to_string) else {
+                    continue;
+                };
+                if let Some(obj) = entry.as_object_mut() {
+                    obj.insert("__file_path".to_string(), json!(file_path));
+                }
+                entries.insert(name, entry);
+            }
+        }
+        StructureForm::Files => {
+            if !structure_root.exists() {
+                return Ok(entries);
+            }
+            for dir_entry in walkdir::WalkDir::new(structure_root)
+                .into_iter()
+                .filter_map(|e| e.ok())
+            {
+                let path = dir_entry.path();
+                if path.extension().map_or(false, |ext| ext == "md") {
+                    let Ok(frontmatter) = parse_frontmatter(path) else {
+                        continue;
+                    };
+                    let mut entry = json_from_frontmatter(&frontmatter);
+                    let Some(name) = entry.get(name_field).and_then(|v| v.as_str()).map(str::to_string)
+                    else {
+                        continue;
+                    };
+
+                    let meta_path = path.with_extension("meta.verilib");
+                    if let Ok(meta_content) = std::fs::read_to_string(&meta_path) {
+                        if let Ok(meta) = serde_json::from_str::<HashMap<String, Value>>(&meta_content) {
+                            if let Some(obj) = entry.as_object_mut() {
+                                for (key, value) in meta {
+                                    obj.insert(key, value);
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(obj) = entry.as_object_mut() {
+                        obj.insert("__file_path".to_string(), json!(path.display().to_string()));
+                    }
+                    entries.insert(name, entry);
+                }
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Classic O(len_a * len_b) edit distance between two strings. Shared by
+/// `search`'s typo-tolerant matching and `atomize`'s "did you mean"
+/// suggestions for stale code-name/code-path lookups.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// Convert a frontmatter map into a `Value::Object` for field lookups.
+fn json_from_frontmatter(frontmatter: &HashMap<String, Value>) -> Value {
+    Value::Object(frontmatter.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+}
+
 /// Parse YAML frontmatter from a markdown file.
 pub fn parse_frontmatter(path: &Path) -> Result<HashMap<String, Value>> {
     let content = std::fs::read_to_string(path)?;
@@ -192,24 +409,28 @@ pub fn parse_frontmatter(path: &Path) -> Result<HashMap<String, Value>> {
     Ok(frontmatter)
 }
 
-/// Write a markdown file with YAML frontmatter.
-pub fn write_frontmatter_file(
-    path: &Path,
-    metadata: &HashMap<String, Value>,
-    body: Option<&str>,
-) -> Result<()> {
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
+/// Deserialize an already-parsed frontmatter map into a typed value `T`,
+/// for callers that want schema-validated field access (missing/malformed
+/// required fields surface as an `Err`) instead of ad-hoc `Value` lookups
+/// on the raw map.
+pub fn frontmatter_to_typed<T: DeserializeOwned>(frontmatter: &HashMap<String, Value>) -> Result<T> {
+    serde_json::from_value(json_from_frontmatter(frontmatter)).context("Frontmatter failed schema validation")
+}
+
+/// Render YAML frontmatter plus a body into a canonical markdown document.
+///
+/// Keys are sorted for stable ordering and values are serialized by
+/// `serde_yaml` rather than hand-rolled quoting rules, so nested objects
+/// and arrays round-trip correctly and the output is identical whether the
+/// file was written by this tool or by `fmt` normalizing a hand-edited one.
+pub fn render_frontmatter(metadata: &HashMap<String, Value>, body: Option<&str>) -> Result<String> {
+    let sorted: BTreeMap<&String, &Value> = metadata.iter().collect();
+    let yaml = serde_yaml::to_string(&sorted).context("Failed to serialize frontmatter")?;
 
     let mut content = String::new();
     content.push_str("---\n");
-
-    for (key, value) in metadata {
-        let formatted = format_yaml_value(value)?;
-        content.push_str(&format!("{}: {}\n", key, formatted));
-    }
-
+    content.push_str(yaml.trim_end());
+    content.push('\n');
     content.push_str("---\n");
     content.push('\n');
 
@@ -218,56 +439,88 @@ pub fn write_frontmatter_file(
         content.push('\n');
     }
 
+    Ok(content)
+}
+
+/// Write a markdown file with canonical YAML frontmatter.
+pub fn write_frontmatter_file(
+    path: &Path,
+    metadata: &HashMap<String, Value>,
+    body: Option<&str>,
+) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let content = render_frontmatter(metadata, body)?;
     std::fs::write(path, content)?;
     Ok(())
 }
 
-/// Format a JSON value as a YAML scalar.
-fn format_yaml_value(value: &Value) -> Result<String> {
-    match value {
-        Value::Null => Ok("null".to_string()),
-        Value::Bool(b) => Ok(if *b { "true" } else { "false" }.to_string()),
-        Value::Number(n) => Ok(n.to_string()),
-        Value::String(s) => {
-            // Check if string needs quoting
-            if s.is_empty()
-                || s == "null"
-                || s == "true"
-                || s == "false"
-                || s == "~"
-                || s.starts_with('{')
-                || s.starts_with('[')
-                || s.starts_with('\'')
-                || s.starts_with('"')
-                || s.starts_with('|')
-                || s.starts_with('>')
-                || s.starts_with('*')
-                || s.starts_with('&')
-                || s.starts_with('!')
-                || s.contains(':')
-                || s.contains('#')
-                || s.contains('\n')
-            {
-                let escaped = s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
-                Ok(format!("\"{}\"", escaped))
-            } else {
-                Ok(s.clone())
-            }
-        }
-        Value::Array(arr) => {
-            let items: Result<Vec<String>> = arr.iter().map(format_yaml_value).collect();
-            Ok(format!("[{}]", items?.join(", ")))
+/// Run `write_one` over `items` across a rayon thread pool, keyed by a
+/// stable sort key (a relative file path in practice). Each call may
+/// return a warning string instead of (or alongside) writing its file;
+/// warnings are buffered and printed sorted by key once every item is
+/// done, so stderr output stays deterministic despite the pool's
+/// non-deterministic completion order. Returns the number of items
+/// processed, or the first error encountered.
+pub fn write_files_parallel<T, F>(items: Vec<(String, T)>, write_one: F) -> Result<usize>
+where
+    T: Send,
+    F: Fn(&str, T) -> Result<Option<String>> + Sync,
+{
+    use rayon::prelude::*;
+
+    let results: Vec<(String, Result<Option<String>>)> = items
+        .into_par_iter()
+        .map(|(key, item)| {
+            let result = write_one(&key, item);
+            (key, result)
+        })
+        .collect();
+
+    let mut warnings = Vec::new();
+    let mut count = 0;
+    for (key, result) in results {
+        if let Some(warning) = result? {
+            warnings.push((key, warning));
         }
-        Value::Object(_) => bail!("Nested objects are not supported in metadata"),
+        count += 1;
     }
+
+    warnings.sort();
+    for (_, warning) in &warnings {
+        eprintln!("{}", warning);
+    }
+
+    Ok(count)
 }
 
-/// Run an external command and return its output.
+/// Render a program + arguments as a displayable command line, e.g. `probe-verus atomize -r`.
+fn format_command_line(program: &str, args: &[&str]) -> String {
+    let mut parts = Vec::with_capacity(args.len() + 1);
+    parts.push(program.to_string());
+    parts.extend(args.iter().map(|a| a.to_string()));
+    parts.join(" ")
+}
+
+/// Run an external command, checking its exit status.
+///
+/// In `Verbose` log level the command line is printed before execution. On a
+/// non-zero exit the returned error includes the command line, exit code, and
+/// captured stderr; if the process was terminated by a signal (no exit code
+/// is available) the error says so instead.
 pub fn run_command(
     program: &str,
     args: &[&str],
     cwd: Option<&Path>,
 ) -> Result<std::process::Output> {
+    let command_line = format_command_line(program, args);
+
+    if log_level() == LogLevel::Verbose {
+        println!("+ {}", command_line);
+    }
+
     let mut cmd = Command::new(program);
     cmd.args(args);
 
@@ -275,10 +528,47 @@ pub fn run_command(
         cmd.current_dir(dir);
     }
 
-    let output = cmd.output().context(format!("Failed to run {}", program))?;
+    let output = cmd
+        .output()
+        .context(format!("Failed to run {}", command_line))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        match output.status.code() {
+            Some(code) => {
+                if stderr.is_empty() {
+                    bail!("{} exited with code {}", command_line, code);
+                } else {
+                    bail!(
+                        "{} exited with code {}\nstderr:\n{}",
+                        command_line,
+                        code,
+                        stderr.trim_end()
+                    );
+                }
+            }
+            None => {
+                bail!("{} terminated by signal", command_line);
+            }
+        }
+    }
+
     Ok(output)
 }
 
+/// Get the version string of an external tool by running `<program> --version`.
+///
+/// Used to stamp cert provenance so a cert can be flagged stale when the
+/// tool that produced it is upgraded.
+pub fn get_tool_version(program: &str) -> Result<String> {
+    let output = run_command(program, &["--version"], None)?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if version.is_empty() {
+        bail!("{} --version produced no output", program);
+    }
+    Ok(version)
+}
+
 /// Display a multiple choice menu and get user selections.
 pub fn display_menu<F>(
     items: &[(String, Value)],